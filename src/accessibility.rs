@@ -0,0 +1,85 @@
+use bevy::{
+    a11y::{
+        accesskit::{Live, NodeBuilder, Role},
+        AccessibilityNode, Focus,
+    },
+    prelude::*,
+};
+
+use crate::{despawn_screen, GameState, Scoreboard};
+
+const SCORE_ANNOUNCE_INTERVAL: f32 = 2.;
+
+#[derive(Component)]
+struct ScoreAnnouncer {
+    timer: Timer,
+    announced: Option<i32>,
+}
+
+pub fn accessibility_plugin(app: &mut App) {
+    app.add_systems(PostUpdate, (sync_button_names, focus_hovered_button))
+        .add_systems(OnEnter(GameState::Game), spawn_score_announcer)
+        .add_systems(Update, announce_score.run_if(in_state(GameState::Game)))
+        .add_systems(OnExit(GameState::Game), despawn_screen::<ScoreAnnouncer>);
+}
+
+// Bevy only names a button from its text when the button is spawned, so
+// labels that change afterwards (setting values, toggles) are refreshed here.
+fn sync_button_names(
+    changed_texts: Query<&Parent, Changed<Text>>,
+    mut button_query: Query<(&Children, &mut AccessibilityNode), With<Button>>,
+    texts: Query<&Text>,
+) {
+    for parent in &changed_texts {
+        let Ok((children, mut node)) = button_query.get_mut(parent.get()) else {
+            continue;
+        };
+        let name = texts
+            .iter_many(children)
+            .flat_map(|text| text.sections.iter().map(|section| section.value.trim()))
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        node.set_name(name);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn focus_hovered_button(
+    interaction_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Button>)>,
+    mut focus: ResMut<Focus>,
+) {
+    for (entity, interaction) in &interaction_query {
+        if *interaction != Interaction::None && focus.0 != Some(entity) {
+            focus.0 = Some(entity);
+        }
+    }
+}
+
+fn spawn_score_announcer(mut commands: Commands) {
+    let mut node = NodeBuilder::new(Role::Status);
+    node.set_live(Live::Polite);
+    commands.spawn((
+        NodeBundle::default(),
+        AccessibilityNode(node),
+        ScoreAnnouncer {
+            timer: Timer::from_seconds(SCORE_ANNOUNCE_INTERVAL, TimerMode::Repeating),
+            announced: None,
+        },
+    ));
+}
+
+fn announce_score(
+    time: Res<Time<Real>>,
+    scoreboard: Res<Scoreboard>,
+    mut announcer_query: Query<(&mut ScoreAnnouncer, &mut AccessibilityNode)>,
+) {
+    for (mut announcer, mut node) in &mut announcer_query {
+        announcer.timer.tick(time.delta());
+        if !announcer.timer.just_finished() || announcer.announced == Some(scoreboard.score) {
+            continue;
+        }
+        announcer.announced = Some(scoreboard.score);
+        node.set_name(format!("{} points", scoreboard.score));
+    }
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cheats::run_is_clean,
+    persistence::Profile,
+    time_trial::{TimeTrial, TARGET_CATCHES},
+    GameState, RunStats,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Achievement {
+    ComboTwenty,
+    HundredCatches,
+    TimeTrialFinished,
+}
+
+impl Achievement {
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::ComboTwenty => "Reach a 20 combo",
+            Achievement::HundredCatches => "Catch 100 apples",
+            Achievement::TimeTrialFinished => "Finish a time trial",
+        }
+    }
+}
+
+pub fn achievements_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        check_run_achievements.run_if(in_state(GameState::Game).and_then(run_is_clean)),
+    )
+    .add_systems(
+        OnEnter(GameState::Results),
+        check_time_trial_achievement.run_if(run_is_clean),
+    );
+}
+
+fn unlock(profile: &mut ResMut<Profile>, achievement: Achievement) {
+    if !profile.has_achievement(achievement) {
+        profile.achievements.push(achievement);
+    }
+}
+
+fn check_run_achievements(run_stats: Res<RunStats>, mut profile: ResMut<Profile>) {
+    if run_stats.best_combo >= 20 {
+        unlock(&mut profile, Achievement::ComboTwenty);
+    }
+    if profile.lifetime_catches >= 100 {
+        unlock(&mut profile, Achievement::HundredCatches);
+    }
+}
+
+fn check_time_trial_achievement(time_trial: Option<Res<TimeTrial>>, mut profile: ResMut<Profile>) {
+    if time_trial.is_some_and(|time_trial| time_trial.catches >= TARGET_CATCHES) {
+        unlock(&mut profile, Achievement::TimeTrialFinished);
+    }
+}
@@ -0,0 +1,164 @@
+use bevy::{input::InputSystem, prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::ActionState;
+
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct ReadActions;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Pause,
+    Rewind,
+    Dash,
+    Mute,
+    Defuse,
+}
+
+#[derive(Resource, Serialize, Deserialize)]
+pub struct InputBindings(pub HashMap<Action, Vec<KeyCode>>);
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self(HashMap::from([
+            (Action::MoveLeft, vec![KeyCode::KeyA, KeyCode::ArrowLeft]),
+            (Action::MoveRight, vec![KeyCode::KeyD, KeyCode::ArrowRight]),
+            (Action::Pause, vec![KeyCode::Escape]),
+            (Action::Rewind, vec![KeyCode::KeyR]),
+            (Action::Dash, vec![KeyCode::ShiftLeft, KeyCode::ShiftRight]),
+            (Action::Mute, vec![KeyCode::KeyM]),
+            (Action::Defuse, vec![KeyCode::Space]),
+        ]))
+    }
+}
+
+impl InputBindings {
+    // Actions added since the bindings were saved get their default keys.
+    pub fn with_defaults(mut self) -> Self {
+        for (action, keys) in Self::default().0 {
+            self.0.entry(action).or_insert(keys);
+        }
+        self
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyCode] {
+        self.0.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    // Makes `key` the primary key for `action`. An action that already used
+    // the key takes over the replaced one, so nothing is left unbound.
+    pub fn rebind(&mut self, action: Action, key: KeyCode) {
+        let previous = self.keys(action).first().copied();
+        for (_, keys) in self.0.iter_mut().filter(|(other, _)| **other != action) {
+            match (keys.iter().position(|bound| *bound == key), previous) {
+                (Some(index), Some(previous)) => keys[index] = previous,
+                (Some(index), None) => {
+                    keys.remove(index);
+                }
+                (None, _) => {}
+            }
+        }
+        let keys = self.0.entry(action).or_default();
+        match keys.iter().position(|bound| *bound == key) {
+            Some(index) => keys.swap(0, index),
+            None if keys.is_empty() => keys.push(key),
+            None => keys[0] = key,
+        }
+    }
+
+    fn pressed(&self, input: &ButtonInput<KeyCode>, action: Action) -> bool {
+        input.any_pressed(self.keys(action).iter().copied())
+    }
+
+    fn just_pressed(&self, input: &ButtonInput<KeyCode>, action: Action) -> bool {
+        input.any_just_pressed(self.keys(action).iter().copied())
+    }
+}
+
+// The horizontal direction pressed most recently, so holding both sides
+// moves towards whichever was pressed last.
+#[derive(Resource, Default)]
+struct LastDirection(Option<Action>);
+
+// How far the left stick has to be pushed before the basket moves.
+#[derive(Resource)]
+pub struct StickDeadzone(pub f32);
+
+impl Default for StickDeadzone {
+    fn default() -> Self {
+        Self(0.3)
+    }
+}
+
+pub fn actions_plugin(app: &mut App) {
+    app.init_resource::<LastDirection>()
+        .init_resource::<StickDeadzone>()
+        .add_systems(
+            PreUpdate,
+            (keyboard_actions, gamepad_actions)
+                .chain()
+                .in_set(ReadActions)
+                .after(InputSystem),
+        );
+}
+
+fn keyboard_actions(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut last_direction: ResMut<LastDirection>,
+    mut actions: ResMut<ActionState>,
+) {
+    for action in [Action::MoveLeft, Action::MoveRight] {
+        if bindings.just_pressed(&keyboard_input, action) {
+            last_direction.0 = Some(action);
+        }
+    }
+    let mut left = bindings.pressed(&keyboard_input, Action::MoveLeft);
+    let mut right = bindings.pressed(&keyboard_input, Action::MoveRight);
+    if left && right {
+        left = last_direction.0 == Some(Action::MoveLeft);
+        right = !left;
+    }
+    *actions = ActionState {
+        left,
+        right,
+        pause: bindings.just_pressed(&keyboard_input, Action::Pause),
+        rewind: bindings.just_pressed(&keyboard_input, Action::Rewind),
+        dash: bindings.just_pressed(&keyboard_input, Action::Dash),
+        mute: bindings.just_pressed(&keyboard_input, Action::Mute),
+        defuse: bindings.just_pressed(&keyboard_input, Action::Defuse),
+        ..default()
+    };
+}
+
+// Every connected gamepad feeds the same actions, so one plugged in
+// mid-game just starts working.
+fn gamepad_actions(
+    gamepads: Res<Gamepads>,
+    buttons: Res<ButtonInput<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    deadzone: Res<StickDeadzone>,
+    mut actions: ResMut<ActionState>,
+) {
+    for gamepad in gamepads.iter() {
+        let button = |button_type| GamepadButton::new(gamepad, button_type);
+        let stick_x = axes
+            .get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickX))
+            .unwrap_or(0.);
+        let left = buttons.pressed(button(GamepadButtonType::DPadLeft)) || stick_x < -deadzone.0;
+        let right = buttons.pressed(button(GamepadButtonType::DPadRight)) || stick_x > deadzone.0;
+        // The keyboard keeps priority while it is steering.
+        if !actions.left && !actions.right && left != right {
+            actions.left = left;
+            actions.right = right;
+        }
+        actions.pause |= buttons.just_pressed(button(GamepadButtonType::Start));
+        actions.dash |= buttons.just_pressed(button(GamepadButtonType::RightTrigger));
+        actions.defuse |= buttons.just_pressed(button(GamepadButtonType::West));
+        actions.menu_up |= buttons.just_pressed(button(GamepadButtonType::DPadUp));
+        actions.menu_down |= buttons.just_pressed(button(GamepadButtonType::DPadDown));
+        actions.confirm |= buttons.just_pressed(button(GamepadButtonType::South));
+    }
+}
@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen, frenzy::FrenzyStartedEvent, ComboMilestoneEvent, FontAssets, GameState,
+    MotionPreferences, PauseMode, COMBO_MILESTONES,
+};
+
+const ANNOUNCEMENT_DURATION: f32 = 0.7;
+const MILESTONE_WORDS: [&str; 4] = ["Nice!", "Great!", "Amazing!", "Unstoppable!"];
+const FRENZY_WORD: &str = "Golden Frenzy!";
+
+#[derive(Component)]
+struct Announcement {
+    timer: Timer,
+    animated: bool,
+}
+
+#[derive(Resource, Default)]
+struct AnnouncementQueue(VecDeque<&'static str>);
+
+pub fn announcer_plugin(app: &mut App) {
+    app.init_resource::<AnnouncementQueue>()
+        .add_systems(
+            Update,
+            (queue_announcements, show_announcement, animate_announcement)
+                .chain()
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        )
+        .add_systems(
+            OnExit(GameState::Game),
+            (despawn_screen::<Announcement>, clear_queue),
+        );
+}
+
+fn queue_announcements(
+    mut combo_events: EventReader<ComboMilestoneEvent>,
+    mut frenzy_events: EventReader<FrenzyStartedEvent>,
+    mut queue: ResMut<AnnouncementQueue>,
+) {
+    for event in combo_events.read() {
+        if let Some(index) = COMBO_MILESTONES.iter().position(|&m| m == event.combo) {
+            queue.0.push_back(MILESTONE_WORDS[index]);
+        }
+    }
+    for _ in frenzy_events.read() {
+        queue.0.push_back(FRENZY_WORD);
+    }
+}
+
+fn show_announcement(
+    mut commands: Commands,
+    mut queue: ResMut<AnnouncementQueue>,
+    announcement_query: Query<(), With<Announcement>>,
+    motion_preferences: Res<MotionPreferences>,
+    fonts: Res<FontAssets>,
+) {
+    if !announcement_query.is_empty() {
+        return;
+    }
+    let Some(word) = queue.0.pop_front() else {
+        return;
+    };
+    let animated = !motion_preferences.reduce_motion;
+    let (font_size, scale) = if animated { (80., 0.5) } else { (40., 1.) };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            Announcement {
+                timer: Timer::from_seconds(ANNOUNCEMENT_DURATION, TimerMode::Once),
+                animated,
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text::from_section(
+                    word,
+                    TextStyle {
+                        font: fonts.bold.clone(),
+                        font_size,
+                        ..default()
+                    },
+                )
+                .with_justify(JustifyText::Center),
+                transform: Transform::from_scale(Vec3::splat(scale)),
+                ..default()
+            });
+        });
+}
+
+fn animate_announcement(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut announcement_query: Query<(Entity, &mut Announcement, &Children)>,
+    mut text_query: Query<(&mut Text, &mut Transform)>,
+) {
+    for (entity, mut announcement, children) in &mut announcement_query {
+        announcement.timer.tick(time.delta());
+        if announcement.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        if !announcement.animated {
+            continue;
+        }
+
+        let progress = announcement.timer.fraction();
+        for &child in children {
+            if let Ok((mut text, mut transform)) = text_query.get_mut(child) {
+                transform.scale = Vec3::splat(0.5 + 0.7 * (1. - (1. - progress).powi(3)));
+                for section in &mut text.sections {
+                    section.style.color.set_alpha(1. - progress * progress);
+                }
+            }
+        }
+    }
+}
+
+fn clear_queue(mut queue: ResMut<AnnouncementQueue>) {
+    queue.0.clear();
+}
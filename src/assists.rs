@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{AssistSettings, GameState, PauseMode, RunAssists};
+
+pub fn assists_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Game), apply_game_speed)
+        .add_systems(
+            OnExit(PauseMode::Paused),
+            apply_game_speed.run_if(in_state(GameState::Game)),
+        )
+        .add_systems(OnExit(GameState::Game), reset_game_speed);
+}
+
+fn apply_game_speed(
+    assist_settings: Res<AssistSettings>,
+    mut run_assists: ResMut<RunAssists>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    time.set_relative_speed(assist_settings.game_speed as f32 / 100.);
+    run_assists.lowest_game_speed = run_assists
+        .lowest_game_speed
+        .min(assist_settings.game_speed);
+    run_assists.highest_basket_scale = run_assists
+        .highest_basket_scale
+        .max(assist_settings.basket_scale);
+}
+
+// Slowed runs and runs with a bigger basket don't compete with records set
+// without them.
+pub fn run_is_unassisted(run_assists: Res<RunAssists>) -> bool {
+    run_assists.lowest_game_speed >= 100 && run_assists.highest_basket_scale <= 100
+}
+
+fn reset_game_speed(mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(1.);
+}
@@ -0,0 +1,100 @@
+use bevy::{audio::Volume, prelude::*};
+use bevy_asset_loader::asset_collection::AssetCollection;
+
+use crate::{ActionState, AppleCaughtEvent, AppleMissedEvent, AudioSettings, SettingsMenu};
+
+const SFX_VOLUME: f32 = 0.6;
+
+// The master volume from before muting, restored when unmuting.
+#[derive(Resource, Default)]
+struct MutedMaster(Option<f32>);
+
+#[derive(AssetCollection, Resource)]
+pub struct AudioAssets {
+    #[asset(path = "audio/catch.ogg")]
+    pub catch: Handle<AudioSource>,
+    #[asset(path = "audio/miss.ogg")]
+    pub miss: Handle<AudioSource>,
+    #[asset(path = "audio/click.ogg")]
+    pub click: Handle<AudioSource>,
+    #[asset(path = "audio/menu_theme.ogg")]
+    pub menu_theme: Handle<AudioSource>,
+}
+
+pub fn audio_plugin(app: &mut App) {
+    app.init_resource::<MutedMaster>().add_systems(
+        Update,
+        (
+            (play_catch_sounds, play_click_sounds).run_if(resource_exists::<AudioAssets>),
+            // The mute key could be the one being captured for a new binding.
+            toggle_mute.run_if(not(in_state(SettingsMenu::Controls))),
+        ),
+    );
+}
+
+pub fn play_sfx(
+    commands: &mut Commands,
+    sound: &Handle<AudioSource>,
+    audio_settings: &AudioSettings,
+) {
+    commands.spawn(AudioBundle {
+        source: sound.clone(),
+        settings: PlaybackSettings::DESPAWN
+            .with_volume(Volume::new(SFX_VOLUME * audio_settings.sfx_volume())),
+    });
+}
+
+fn toggle_mute(
+    actions: Res<ActionState>,
+    mut audio_settings: ResMut<AudioSettings>,
+    mut muted_master: ResMut<MutedMaster>,
+) {
+    if !actions.mute {
+        return;
+    }
+    match muted_master.0.take() {
+        Some(master) => audio_settings.master = master,
+        None => muted_master.0 = Some(std::mem::take(&mut audio_settings.master)),
+    }
+}
+
+// Each sound plays at most once a frame, however many apples landed in it,
+// since identical copies on top of each other only get louder.
+fn play_catch_sounds(
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<AudioSettings>,
+    mut caught_events: EventReader<AppleCaughtEvent>,
+    mut missed_events: EventReader<AppleMissedEvent>,
+) {
+    let mut caught = false;
+    // A rotten apple is a mistake, so it sounds like one.
+    let mut missed = missed_events.read().count() > 0;
+    for event in caught_events.read() {
+        if event.points < 0 {
+            missed = true;
+        } else {
+            caught = true;
+        }
+    }
+    if caught {
+        play_sfx(&mut commands, &audio_assets.catch, &audio_settings);
+    }
+    if missed {
+        play_sfx(&mut commands, &audio_assets.miss, &audio_settings);
+    }
+}
+
+fn play_click_sounds(
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<AudioSettings>,
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        play_sfx(&mut commands, &audio_assets.click, &audio_settings);
+    }
+}
@@ -0,0 +1,351 @@
+use bevy::{
+    prelude::*,
+    ui::{FocusPolicy, RelativeCursorPosition},
+};
+
+use crate::{
+    despawn_screen,
+    persistence::{store_audio_settings, DataDir},
+    AudioSettings, SettingsMenu, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+const SLIDER_WIDTH: f32 = 300.;
+const SLIDER_STEP: f32 = 0.05;
+const SLIDER_FILL: Color = Color::srgb(0.35, 0.75, 0.35);
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum Channel {
+    #[default]
+    Master,
+    Music,
+    Sfx,
+}
+
+impl Channel {
+    const ALL: [Channel; 3] = [Channel::Master, Channel::Music, Channel::Sfx];
+
+    fn label(self) -> &'static str {
+        match self {
+            Channel::Master => "Master",
+            Channel::Music => "Music",
+            Channel::Sfx => "Effects",
+        }
+    }
+
+    fn value(self, audio_settings: &AudioSettings) -> f32 {
+        match self {
+            Channel::Master => audio_settings.master,
+            Channel::Music => audio_settings.music,
+            Channel::Sfx => audio_settings.sfx,
+        }
+    }
+
+    fn set(self, audio_settings: &mut ResMut<AudioSettings>, value: f32) {
+        let value = value.clamp(0., 1.);
+        // Only touch the settings on a real change, so music volumes aren't
+        // reapplied every frame a slider is held.
+        if self.value(audio_settings) == value {
+            return;
+        }
+        match self {
+            Channel::Master => audio_settings.master = value,
+            Channel::Music => audio_settings.music = value,
+            Channel::Sfx => audio_settings.sfx = value,
+        }
+    }
+}
+
+#[derive(Component)]
+struct OnAudioMenuScreen;
+
+#[derive(Component)]
+struct Slider(Channel);
+
+#[derive(Component)]
+struct SliderFill(Channel);
+
+#[derive(Component)]
+struct SliderValueText(Channel);
+
+#[derive(Component)]
+enum MenuButtonAction {
+    Back,
+}
+
+// The slider the arrow keys adjust.
+#[derive(Resource, Default)]
+struct SelectedSlider(Channel);
+
+pub fn audio_menu_plugin(app: &mut App) {
+    app.init_resource::<SelectedSlider>()
+        .add_systems(OnEnter(SettingsMenu::Audio), setup)
+        .add_systems(
+            Update,
+            (
+                button_system,
+                menu_action,
+                drag_sliders,
+                keyboard_input,
+                update_sliders,
+            )
+                .chain()
+                .run_if(in_state(SettingsMenu::Audio)),
+        )
+        .add_systems(
+            OnExit(SettingsMenu::Audio),
+            (despawn_screen::<OnAudioMenuScreen>, save_audio_settings),
+        );
+}
+
+fn percent(value: f32) -> String {
+    format!("{}%", (value * 100.).round())
+}
+
+fn setup(
+    mut commands: Commands,
+    audio_settings: Res<AudioSettings>,
+    selected: Res<SelectedSlider>,
+) {
+    let text_style = TextStyle {
+        font_size: 36.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.05, 0.05, 0.05, 0.95).into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            OnAudioMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Audio",
+                        TextStyle {
+                            font_size: 60.,
+                            ..default()
+                        },
+                    ));
+
+                    for channel in Channel::ALL {
+                        let value = channel.value(&audio_settings);
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    align_items: AlignItems::Center,
+                                    margin: UiRect::all(Val::Px(10.0)),
+                                    column_gap: Val::Px(20.0),
+                                    ..default()
+                                },
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(
+                                    TextBundle::from_section(channel.label(), text_style.clone())
+                                        .with_style(Style {
+                                            width: Val::Px(140.0),
+                                            ..default()
+                                        }),
+                                );
+                                parent
+                                    .spawn((
+                                        ButtonBundle {
+                                            style: Style {
+                                                width: Val::Px(SLIDER_WIDTH),
+                                                height: Val::Px(24.0),
+                                                ..default()
+                                            },
+                                            background_color: if channel == selected.0 {
+                                                HOVERED_BUTTON
+                                            } else {
+                                                NORMAL_BUTTON
+                                            }
+                                            .into(),
+                                            ..default()
+                                        },
+                                        RelativeCursorPosition::default(),
+                                        Slider(channel),
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn((
+                                            NodeBundle {
+                                                style: Style {
+                                                    width: Val::Percent(value * 100.),
+                                                    height: Val::Percent(100.0),
+                                                    ..default()
+                                                },
+                                                background_color: SLIDER_FILL.into(),
+                                                focus_policy: FocusPolicy::Pass,
+                                                ..default()
+                                            },
+                                            SliderFill(channel),
+                                        ));
+                                    });
+                                parent.spawn((
+                                    TextBundle::from_section(percent(value), text_style.clone())
+                                        .with_style(Style {
+                                            width: Val::Px(90.0),
+                                            ..default()
+                                        }),
+                                    SliderValueText(channel),
+                                ));
+                            });
+                    }
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    width: Val::Px(400.0),
+                                    height: Val::Px(48.0),
+                                    margin: UiRect::all(Val::Px(5.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Back,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section("Back", text_style.clone()));
+                        });
+                });
+        });
+}
+
+fn drag_sliders(
+    slider_query: Query<(&Interaction, &RelativeCursorPosition, &Slider)>,
+    mut audio_settings: ResMut<AudioSettings>,
+    mut selected: ResMut<SelectedSlider>,
+) {
+    for (interaction, cursor, Slider(channel)) in &slider_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(position) = cursor.normalized {
+            channel.set(&mut audio_settings, position.x);
+        }
+        if selected.0 != *channel {
+            selected.0 = *channel;
+        }
+    }
+}
+
+fn keyboard_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut audio_settings: ResMut<AudioSettings>,
+    mut selected: ResMut<SelectedSlider>,
+    mut settings_state: ResMut<NextState<SettingsMenu>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        settings_state.set(SettingsMenu::Open);
+        return;
+    }
+    let index = Channel::ALL
+        .iter()
+        .position(|&channel| channel == selected.0)
+        .unwrap_or(0);
+    if keyboard_input.just_pressed(KeyCode::ArrowUp) && index > 0 {
+        selected.0 = Channel::ALL[index - 1];
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) && index + 1 < Channel::ALL.len() {
+        selected.0 = Channel::ALL[index + 1];
+    }
+    let channel = selected.0;
+    let value = channel.value(&audio_settings);
+    if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+        channel.set(&mut audio_settings, value - SLIDER_STEP);
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+        channel.set(&mut audio_settings, value + SLIDER_STEP);
+    }
+}
+
+fn update_sliders(
+    audio_settings: Res<AudioSettings>,
+    selected: Res<SelectedSlider>,
+    mut slider_query: Query<(&mut BackgroundColor, &Slider)>,
+    mut fill_query: Query<(&mut Style, &SliderFill)>,
+    mut text_query: Query<(&mut Text, &SliderValueText)>,
+) {
+    if !audio_settings.is_changed() && !selected.is_changed() {
+        return;
+    }
+    for (mut color, Slider(channel)) in &mut slider_query {
+        *color = if *channel == selected.0 {
+            HOVERED_BUTTON
+        } else {
+            NORMAL_BUTTON
+        }
+        .into();
+    }
+    for (mut style, SliderFill(channel)) in &mut fill_query {
+        style.width = Val::Percent(channel.value(&audio_settings) * 100.);
+    }
+    for (mut text, SliderValueText(channel)) in &mut text_query {
+        text.sections[0].value = percent(channel.value(&audio_settings));
+    }
+}
+
+fn save_audio_settings(dir: Res<DataDir>, audio_settings: Res<AudioSettings>) {
+    store_audio_settings(&dir, &audio_settings);
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<MenuButtonAction>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut settings_state: ResMut<NextState<SettingsMenu>>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Back => {
+                    settings_state.set(SettingsMenu::Open);
+                }
+            }
+        }
+    }
+}
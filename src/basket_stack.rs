@@ -0,0 +1,164 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::Player,
+    persistence::Profile,
+    textures::{Texture, Textures},
+    AppleCaughtEvent, GameState, MotionPreferences, PauseMode,
+};
+
+const SLOTS: [Vec2; 4] = [
+    Vec2::new(-30., 22.),
+    Vec2::new(10., 26.),
+    Vec2::new(-8., 30.),
+    Vec2::new(32., 21.),
+];
+const STACKED_APPLE_SCALE: f32 = 0.3;
+const FADE_DURATION: f32 = 0.3;
+const JIGGLE_STIFFNESS: f32 = 300.;
+const JIGGLE_DAMPING: f32 = 12.;
+const JIGGLE_RESPONSE: f32 = 0.0015;
+const MAX_JIGGLE: f32 = 6.;
+
+#[derive(Component)]
+struct StackedApple {
+    slot: usize,
+}
+
+#[derive(Component)]
+struct FadingOut(Timer);
+
+#[derive(Resource, Default)]
+struct BasketStack {
+    next_slot: usize,
+    last_x: Option<f32>,
+    last_velocity: f32,
+    offset: f32,
+    offset_velocity: f32,
+}
+
+pub fn basket_stack_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Game), reset_stack)
+        .add_systems(
+            Update,
+            (add_caught_apples, jiggle, fade_out)
+                .chain()
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        );
+}
+
+fn reset_stack(mut commands: Commands) {
+    commands.insert_resource(BasketStack::default());
+}
+
+fn add_caught_apples(
+    mut commands: Commands,
+    mut caught_events: EventReader<AppleCaughtEvent>,
+    mut stack: ResMut<BasketStack>,
+    textures: Textures,
+    profile: Res<Profile>,
+    player_query: Query<Entity, With<Player>>,
+    stacked_query: Query<(Entity, &StackedApple), Without<FadingOut>>,
+) {
+    let player = match player_query.get_single() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+    for _ in caught_events.read().filter(|event| event.points >= 0) {
+        let Some((texture, size)) = textures.sprite(Texture::Apple) else {
+            return;
+        };
+        let slot = stack.next_slot;
+        stack.next_slot = (slot + 1) % SLOTS.len();
+
+        for (entity, stacked) in &stacked_query {
+            if stacked.slot == slot {
+                commands
+                    .entity(entity)
+                    .insert(FadingOut(Timer::from_seconds(
+                        FADE_DURATION,
+                        TimerMode::Once,
+                    )));
+            }
+        }
+
+        let mut stacked_apple = commands.spawn((
+            SpriteBundle {
+                transform: Transform {
+                    translation: SLOTS[slot].extend(-0.5),
+                    scale: Vec3::splat(STACKED_APPLE_SCALE),
+                    ..default()
+                },
+                texture,
+                sprite: Sprite {
+                    color: profile.active_apple_theme().tint(),
+                    custom_size: Some(size),
+                    ..default()
+                },
+                ..default()
+            },
+            StackedApple { slot },
+        ));
+        // Resting apples hold the first frame rather than shining.
+        if let Some(atlas) = textures.atlas(Texture::Apple) {
+            stacked_apple.insert(atlas);
+        }
+        let stacked_apple = stacked_apple.id();
+        commands.entity(player).add_child(stacked_apple);
+    }
+}
+
+// The stack lags behind the basket like a damped spring pushed by the
+// basket's horizontal acceleration.
+fn jiggle(
+    time: Res<Time>,
+    motion_preferences: Res<MotionPreferences>,
+    mut stack: ResMut<BasketStack>,
+    player_query: Query<&Transform, With<Player>>,
+    mut stacked_query: Query<(&mut Transform, &StackedApple), Without<Player>>,
+) {
+    let dt = time.delta_seconds();
+    let player_x = match player_query.get_single() {
+        Ok(transform) => transform.translation.x,
+        Err(_) => return,
+    };
+    if dt <= 0. {
+        return;
+    }
+
+    let velocity = stack.last_x.map_or(0., |last_x| (player_x - last_x) / dt);
+    let acceleration = (velocity - stack.last_velocity) / dt;
+    stack.last_x = Some(player_x);
+    stack.last_velocity = velocity;
+
+    if motion_preferences.reduce_motion {
+        stack.offset = 0.;
+        stack.offset_velocity = 0.;
+    } else {
+        let force = -acceleration * JIGGLE_RESPONSE * JIGGLE_STIFFNESS
+            - stack.offset * JIGGLE_STIFFNESS
+            - stack.offset_velocity * JIGGLE_DAMPING;
+        stack.offset_velocity += force * dt;
+        stack.offset = (stack.offset + stack.offset_velocity * dt).clamp(-MAX_JIGGLE, MAX_JIGGLE);
+    }
+
+    for (mut transform, stacked) in &mut stacked_query {
+        let slot = SLOTS[stacked.slot];
+        let weight = 1. + (slot.y - SLOTS[0].y) / 10.;
+        transform.translation.x = slot.x + stack.offset * weight;
+    }
+}
+
+fn fade_out(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut fading_query: Query<(Entity, &mut FadingOut, &mut Sprite)>,
+) {
+    for (entity, mut fading, mut sprite) in &mut fading_query {
+        fading.0.tick(time.delta());
+        sprite.color.set_alpha(fading.0.fraction_remaining());
+        if fading.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use bevy::{
+    math::bounding::{Aabb2d, IntersectsVolume},
+    prelude::*,
+};
+use rand::Rng;
+
+use crate::{
+    game::{Apple, Falling, GameTick, OnGameScreen, Player, SpriteSize, Velocity},
+    interpolation::PreviousTransform,
+    textures::{Texture, Textures},
+    GameRng, GameState, PauseMode, PlayArea,
+};
+
+const FIRST_BIRD_DELAY: f32 = 20.;
+const BIRD_INTERVAL: (f32, f32) = (12., 25.);
+const BIRD_SPEED: (f32, f32) = (180., 260.);
+const BIRD_SCALE: f32 = 0.75;
+// In front of the apples, behind the basket.
+const BIRD_Z: f32 = 0.5;
+const FEATHER_COUNT: usize = 6;
+const FEATHER_SPEED: f32 = 90.;
+const FEATHER_FALL: f32 = 150.;
+const FEATHER_LIFETIME: f32 = 0.6;
+const FEATHER_SIZE: Vec2 = Vec2::new(8., 3.);
+const FEATHER_COLOR: Color = Color::srgb(0.95, 0.95, 0.9);
+
+#[derive(Component)]
+pub struct Bird;
+
+#[derive(Component)]
+struct Feather {
+    velocity: Vec2,
+    timer: Timer,
+}
+
+// Times the next bird like `AppleSpawnerConfig` does apples, rolling the
+// wait after each one from the run's seed.
+#[derive(Resource)]
+struct BirdSpawnerConfig {
+    timer: Timer,
+}
+
+impl Default for BirdSpawnerConfig {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(FIRST_BIRD_DELAY, TimerMode::Once),
+        }
+    }
+}
+
+pub fn birds_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Game), reset_bird_spawner)
+        .add_systems(
+            FixedUpdate,
+            (bird_spawning, snatch_apples, despawn_birds)
+                .chain()
+                // Birds fly on `Velocity`, which the game tick applies.
+                .after(GameTick)
+                .run_if(
+                    in_state(GameState::Game)
+                        .and_then(in_state(PauseMode::Playing))
+                        .and_then(any_with_component::<Player>),
+                ),
+        )
+        .add_systems(
+            Update,
+            update_feathers
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        );
+}
+
+fn reset_bird_spawner(mut commands: Commands) {
+    commands.insert_resource(BirdSpawnerConfig::default());
+}
+
+fn bird_spawning(
+    mut commands: Commands,
+    time: Res<Time>,
+    textures: Textures,
+    play_area: Res<PlayArea>,
+    mut spawner: ResMut<BirdSpawnerConfig>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    spawner.timer.tick(time.delta());
+    if !spawner.timer.just_finished() {
+        return;
+    }
+    let rng = &mut game_rng.rng;
+    let delay = rng.gen_range(BIRD_INTERVAL.0..=BIRD_INTERVAL.1);
+    spawner.timer = Timer::from_seconds(delay, TimerMode::Once);
+
+    let Some((_, texture_size)) = textures.sprite(Texture::Bird) else {
+        return;
+    };
+    let size = texture_size * BIRD_SCALE;
+    // Anywhere in the upper third, starting just out of sight on either side.
+    let top = play_area.height / 2. - size.y / 2.;
+    let y = rng.gen_range(top - play_area.height / 3. ..=top);
+    let edge = (play_area.width + size.x) / 2.;
+    let speed = rng.gen_range(BIRD_SPEED.0..=BIRD_SPEED.1);
+    let from_left = rng.gen_bool(0.5);
+    let (x, speed) = if from_left {
+        (-edge, speed)
+    } else {
+        (edge, -speed)
+    };
+    spawn_bird(
+        &mut commands,
+        &textures,
+        Vec3::new(x, y, BIRD_Z),
+        Vec2::new(speed, 0.),
+    );
+}
+
+pub fn spawn_bird(
+    commands: &mut Commands,
+    textures: &Textures,
+    translation: Vec3,
+    velocity: Vec2,
+) -> Option<Entity> {
+    let (texture, texture_size) = textures.sprite(Texture::Bird)?;
+    let bird = commands.spawn((
+        SpriteBundle {
+            transform: Transform {
+                translation,
+                scale: Vec3::new(BIRD_SCALE, BIRD_SCALE, 1.),
+                ..default()
+            },
+            texture,
+            sprite: Sprite {
+                custom_size: Some(texture_size),
+                // The texture faces right.
+                flip_x: velocity.x < 0.,
+                ..default()
+            },
+            ..default()
+        },
+        Bird,
+        Velocity(velocity),
+        SpriteSize(texture_size),
+        PreviousTransform::new(translation),
+        OnGameScreen,
+    ));
+    Some(bird.id())
+}
+
+// Only apples are taken; bombs and the basket are left alone.
+#[allow(clippy::type_complexity)]
+fn snatch_apples(
+    mut commands: Commands,
+    bird_query: Query<(&Transform, &SpriteSize), With<Bird>>,
+    apple_query: Query<(Entity, &Transform, &SpriteSize), (With<Apple>, With<Falling>)>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let mut snatched = Vec::new();
+    for (bird_transform, bird_size) in &bird_query {
+        let bird_aabb = Aabb2d::new(
+            bird_transform.translation.truncate(),
+            bird_size.0 * bird_transform.scale.truncate() / 2.,
+        );
+        for (entity, transform, size) in &apple_query {
+            let apple_aabb = Aabb2d::new(
+                transform.translation.truncate(),
+                size.0 * transform.scale.truncate() / 2.,
+            );
+            if !snatched.contains(&entity) && bird_aabb.intersects(&apple_aabb) {
+                snatched.push(entity);
+                commands.entity(entity).despawn();
+                spawn_feathers(&mut commands, &mut game_rng.rng, transform.translation);
+            }
+        }
+    }
+}
+
+fn spawn_feathers(commands: &mut Commands, rng: &mut impl Rng, position: Vec3) {
+    for _ in 0..FEATHER_COUNT {
+        let direction = Vec2::from_angle(rng.gen_range(0.0..std::f32::consts::TAU));
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(position.with_z(BIRD_Z))
+                    .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+                sprite: Sprite {
+                    color: FEATHER_COLOR,
+                    custom_size: Some(FEATHER_SIZE),
+                    ..default()
+                },
+                ..default()
+            },
+            Feather {
+                velocity: direction * FEATHER_SPEED * rng.gen_range(0.5..=1.),
+                timer: Timer::from_seconds(FEATHER_LIFETIME, TimerMode::Once),
+            },
+            OnGameScreen,
+        ));
+    }
+}
+
+// Only once a bird is heading away from the screen can it be gone for good,
+// since every bird starts out of sight.
+fn despawn_birds(
+    mut commands: Commands,
+    play_area: Res<PlayArea>,
+    bird_query: Query<(Entity, &Transform, &SpriteSize, &Velocity), With<Bird>>,
+) {
+    for (entity, transform, size, velocity) in &bird_query {
+        let edge = (play_area.width + size.0.x * transform.scale.x) / 2.;
+        let x = transform.translation.x;
+        if x * velocity.0.x > 0. && x.abs() > edge {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_feathers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut feather_query: Query<(Entity, &mut Transform, &mut Sprite, &mut Feather)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut transform, mut sprite, mut feather) in &mut feather_query {
+        feather.timer.tick(time.delta());
+        if feather.timer.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        feather.velocity.y -= FEATHER_FALL * delta;
+        transform.translation += (feather.velocity * delta).extend(0.);
+        sprite.color.set_alpha(feather.timer.fraction_remaining());
+    }
+}
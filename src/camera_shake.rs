@@ -0,0 +1,66 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{AppleMissedEvent, CosmeticRng, GameState, MainCamera, MotionPreferences, PauseMode};
+
+const MISS_TRAUMA: f32 = 0.6;
+// A full shake settles in this many seconds.
+const SHAKE_DURATION: f32 = 0.3;
+const MAX_OFFSET: f32 = 12.;
+
+#[derive(Resource, Default)]
+pub struct CameraShake {
+    pub trauma: f32,
+}
+
+pub fn camera_shake_plugin(app: &mut App) {
+    app.init_resource::<CameraShake>()
+        .add_systems(
+            Update,
+            (kick_on_miss, shake_camera)
+                .chain()
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        )
+        .add_systems(OnExit(GameState::Game), reset_camera);
+}
+
+fn kick_on_miss(
+    mut missed_events: EventReader<AppleMissedEvent>,
+    motion_preferences: Res<MotionPreferences>,
+    mut shake: ResMut<CameraShake>,
+) {
+    let misses = missed_events.read().count();
+    if misses > 0 && !motion_preferences.reduce_motion {
+        shake.trauma = (shake.trauma + MISS_TRAUMA * misses as f32).min(1.);
+    }
+}
+
+// Only the camera moves, so the UI stays put while the world shakes.
+fn shake_camera(
+    time: Res<Time>,
+    mut shake: ResMut<CameraShake>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+    mut cosmetic_rng: ResMut<CosmeticRng>,
+) {
+    if shake.trauma <= 0. {
+        return;
+    }
+    shake.trauma = (shake.trauma - time.delta_seconds() / SHAKE_DURATION).max(0.);
+    let strength = MAX_OFFSET * shake.trauma * shake.trauma;
+    let rng = &mut cosmetic_rng.0;
+    for mut transform in &mut camera_query {
+        transform.translation.x = rng.gen_range(-1. ..=1.) * strength;
+        transform.translation.y = rng.gen_range(-1. ..=1.) * strength;
+    }
+}
+
+fn reset_camera(
+    mut shake: ResMut<CameraShake>,
+    mut camera_query: Query<&mut Transform, With<MainCamera>>,
+) {
+    shake.trauma = 0.;
+    for mut transform in &mut camera_query {
+        transform.translation.x = 0.;
+        transform.translation.y = 0.;
+    }
+}
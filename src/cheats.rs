@@ -0,0 +1,120 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{replay::InputSource, toasts::Toast, GameState};
+
+pub const EXTRA_LIVES: u32 = 3;
+// With the gold rush every this many apples is golden.
+pub const GOLD_RUSH_INTERVAL: u32 = 3;
+
+#[derive(Clone, Copy)]
+enum Cheat {
+    BigHead,
+    Applesauce,
+    GoldRush,
+}
+
+impl Cheat {
+    const ALL: [Cheat; 3] = [Cheat::BigHead, Cheat::Applesauce, Cheat::GoldRush];
+
+    fn code(self) -> &'static str {
+        match self {
+            Cheat::BigHead => "bighead",
+            Cheat::Applesauce => "applesauce",
+            Cheat::GoldRush => "goldrush",
+        }
+    }
+
+    fn toast(self) -> &'static str {
+        match self {
+            Cheat::BigHead => "Cheat activated: big basket",
+            Cheat::Applesauce => "Cheat activated: 3 extra lives",
+            Cheat::GoldRush => "Cheat activated: gold rush",
+        }
+    }
+}
+
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Cheats {
+    pub big_basket: bool,
+    pub extra_lives: bool,
+    pub gold_rush: bool,
+    // Set by dev console commands that change the run.
+    pub console_used: bool,
+}
+
+impl Cheats {
+    fn activate(&mut self, cheat: Cheat) {
+        match cheat {
+            Cheat::BigHead => self.big_basket = true,
+            Cheat::Applesauce => self.extra_lives = true,
+            Cheat::GoldRush => self.gold_rush = true,
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        self.big_basket || self.extra_lives || self.gold_rush || self.console_used
+    }
+}
+
+// Cheated runs and replays don't count towards records or achievements.
+pub fn run_is_clean(cheats: Res<Cheats>, input_source: Res<InputSource>) -> bool {
+    !cheats.any() && !input_source.is_replay()
+}
+
+#[derive(Resource, Default)]
+struct TypedLetters(String);
+
+pub fn cheats_plugin(app: &mut App) {
+    app.init_resource::<Cheats>()
+        .init_resource::<TypedLetters>()
+        .add_systems(OnEnter(GameState::MainMenu), reset_cheats)
+        .add_systems(Update, detect_cheats.run_if(in_state(GameState::MainMenu)));
+}
+
+fn reset_cheats(mut cheats: ResMut<Cheats>, mut typed: ResMut<TypedLetters>) {
+    *cheats = Cheats::default();
+    typed.0.clear();
+}
+
+fn detect_cheats(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut typed: ResMut<TypedLetters>,
+    mut cheats: ResMut<Cheats>,
+    mut toasts: EventWriter<Toast>,
+) {
+    let longest_code = Cheat::ALL.iter().map(|cheat| cheat.code().len()).max();
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+        let letters = match &event.logical_key {
+            Key::Character(text) if text.chars().all(|c| c.is_ascii_alphabetic()) => text,
+            _ => {
+                typed.0.clear();
+                continue;
+            }
+        };
+        typed.0.push_str(&letters.to_ascii_lowercase());
+        if let Some(longest_code) = longest_code {
+            let excess = typed.0.len().saturating_sub(longest_code);
+            typed.0.drain(..excess);
+        }
+
+        let Some(cheat) = Cheat::ALL
+            .into_iter()
+            .find(|cheat| typed.0.ends_with(cheat.code()))
+        else {
+            continue;
+        };
+        typed.0.clear();
+        cheats.activate(cheat);
+        toasts.send(Toast::new(cheat.toast()).with_color(Color::srgb(1., 0.85, 0.2)));
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt;
+
+use bevy::{
+    asset::{io::Reader, AssetLoadFailedEvent, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    replay::{InputSource, Replay},
+    AppleSpawnerConfig, Difficulty, DifficultyCurve, DifficultySettings, GameMode, GameState,
+};
+
+const CONFIG_PATH: &str = "config/game.ron";
+
+// Gameplay tuning that can be changed without recompiling. Anything left
+// out of the file keeps its compiled default.
+#[derive(Asset, Resource, TypePath, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameConfig {
+    // The basket's top speed. In smooth movement it takes the acceleration
+    // to get there and slides to a stop under the friction, both in pixels
+    // per second squared.
+    pub player_speed: f32,
+    pub player_acceleration: f32,
+    pub player_friction: f32,
+    pub apple_scale: f32,
+    // Each apple's weight, and so how fast it falls, is scaled by up to
+    // this fraction either way.
+    pub fall_speed_variation: f32,
+    // Apples start out this fast, in pixels per second, and gravity speeds
+    // them up until they reach the terminal speed.
+    pub initial_fall_speed: f32,
+    pub terminal_fall_speed: f32,
+    // Replays the same apples every run when set. Weekly runs keep their
+    // own seed.
+    pub seed: Option<u64>,
+    // Apples hang from a tree across the top of the screen. Turned off,
+    // they appear at random points above it instead.
+    pub apple_tree: bool,
+    easy: DifficultySettings,
+    normal: DifficultySettings,
+    hard: DifficultySettings,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            player_speed: 300.,
+            player_acceleration: 2000.,
+            player_friction: 1600.,
+            apple_scale: 0.5,
+            fall_speed_variation: 0.2,
+            initial_fall_speed: 60.,
+            terminal_fall_speed: 800.,
+            seed: None,
+            apple_tree: true,
+            easy: DifficultySettings {
+                start_spawn_interval: 2.2,
+                end_spawn_interval: 0.9,
+                start_gravity: 55.,
+                end_gravity: 230.,
+                start_spawn_count: 1,
+                end_spawn_count: 1,
+                lives: 5,
+            },
+            normal: DifficultySettings {
+                start_spawn_interval: 1.75,
+                end_spawn_interval: 0.6,
+                start_gravity: 70.,
+                end_gravity: 350.,
+                start_spawn_count: 1,
+                end_spawn_count: 2,
+                lives: 3,
+            },
+            hard: DifficultySettings {
+                start_spawn_interval: 1.4,
+                end_spawn_interval: 0.45,
+                start_gravity: 110.,
+                end_gravity: 520.,
+                start_spawn_count: 1,
+                end_spawn_count: 3,
+                lives: 2,
+            },
+        }
+    }
+}
+
+impl GameConfig {
+    pub fn run_settings(&self, game_mode: GameMode, difficulty: Difficulty) -> DifficultySettings {
+        // Weekly scores are compared against each other, so everyone plays them on Normal.
+        match (game_mode, difficulty) {
+            (GameMode::Weekly, _) | (_, Difficulty::Normal) => self.normal,
+            (_, Difficulty::Easy) => self.easy,
+            (_, Difficulty::Hard) => self.hard,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct ConfigHandle(Handle<GameConfig>);
+
+#[derive(Debug)]
+pub enum ConfigLoadError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for ConfigLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigLoadError::Io(err) => write!(f, "could not read config: {err}"),
+            ConfigLoadError::Ron(err) => write!(f, "could not parse config: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigLoadError {}
+
+#[derive(Default)]
+struct GameConfigLoader;
+
+impl AssetLoader for GameConfigLoader {
+    type Asset = GameConfig;
+    type Settings = ();
+    type Error = ConfigLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<GameConfig, ConfigLoadError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(ConfigLoadError::Io)?;
+        ron::de::from_bytes(&bytes).map_err(ConfigLoadError::Ron)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+// The config isn't part of a loading collection, so a missing or broken
+// file leaves the defaults in place instead of stopping the game.
+pub fn config_plugin(app: &mut App) {
+    app.init_asset::<GameConfig>()
+        .init_asset_loader::<GameConfigLoader>()
+        .init_resource::<GameConfig>()
+        .add_systems(OnEnter(GameState::Loading), load_config)
+        .add_systems(Update, (apply_config, report_config_errors));
+}
+
+fn load_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ConfigHandle(asset_server.load(CONFIG_PATH)));
+}
+
+// With the `dev` feature the file is watched, so edits arrive here as
+// `Modified` while the game is running.
+#[allow(clippy::too_many_arguments)]
+fn apply_config(
+    mut events: EventReader<AssetEvent<GameConfig>>,
+    handle: Option<Res<ConfigHandle>>,
+    configs: Res<Assets<GameConfig>>,
+    mut config: ResMut<GameConfig>,
+    game_mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    curve: Option<ResMut<DifficultyCurve>>,
+    spawner: Option<ResMut<AppleSpawnerConfig>>,
+    mut input_source: ResMut<InputSource>,
+    mut recording: ResMut<Replay>,
+    game_state: Res<State<GameState>>,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+    let mut modified = false;
+    for event in events.read() {
+        if event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0) {
+            if let Some(loaded) = configs.get(&handle.0) {
+                input_source.reload_config(&mut config, loaded.clone());
+            }
+            modified |= event.is_modified(&handle.0);
+        }
+    }
+    if !modified {
+        return;
+    }
+    info!("Reloaded the game config");
+    if input_source.is_replay() {
+        return;
+    }
+    // The recording can't reproduce the change, so it won't be played back.
+    if *game_state.get() == GameState::Game {
+        recording.altered = true;
+    }
+    if let (Some(mut curve), Some(mut spawner)) = (curve, spawner) {
+        retune_run(
+            config.run_settings(*game_mode, *difficulty),
+            &mut curve,
+            &mut spawner,
+        );
+    }
+}
+
+// The run in progress picks up the new numbers straight away. Apples
+// already falling keep their speed, and the spawn timer keeps how far
+// along it was.
+fn retune_run(
+    settings: DifficultySettings,
+    curve: &mut DifficultyCurve,
+    spawner: &mut AppleSpawnerConfig,
+) {
+    let old_interval = curve.spawn_interval();
+    curve.settings = settings;
+    let fraction = spawner.timer.fraction();
+    let duration = spawner
+        .timer
+        .duration()
+        .mul_f32(curve.spawn_interval() / old_interval);
+    spawner.timer.set_duration(duration);
+    spawner.timer.set_elapsed(duration.mul_f32(fraction));
+}
+
+// A failed reload leaves the last good config in place.
+fn report_config_errors(mut failures: EventReader<AssetLoadFailedEvent<GameConfig>>) {
+    for event in failures.read() {
+        warn!(
+            "Keeping the current game config, {}: {}",
+            event.path, event.error
+        );
+    }
+}
@@ -0,0 +1,289 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    actions::{Action, InputBindings},
+    despawn_screen,
+    persistence::{store_bindings, DataDir},
+    SettingsMenu, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+const ACTIONS: [Action; 7] = [
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::Pause,
+    Action::Rewind,
+    Action::Dash,
+    Action::Mute,
+    Action::Defuse,
+];
+
+#[derive(Component)]
+struct OnControlsMenuScreen;
+
+#[derive(Component)]
+struct BindingText(Action);
+
+#[derive(Component)]
+enum MenuButtonAction {
+    Rebind(Action),
+    Reset,
+    Back,
+}
+
+// The action waiting for its next key press, if any.
+#[derive(Resource, Default)]
+struct Capturing(Option<Action>);
+
+pub fn controls_menu_plugin(app: &mut App) {
+    app.init_resource::<Capturing>()
+        .add_systems(OnEnter(SettingsMenu::Controls), setup)
+        .add_systems(
+            Update,
+            (
+                button_system,
+                menu_action,
+                capture_key,
+                keyboard_input,
+                update_labels,
+            )
+                .chain()
+                .run_if(in_state(SettingsMenu::Controls)),
+        )
+        .add_systems(
+            OnExit(SettingsMenu::Controls),
+            (despawn_screen::<OnControlsMenuScreen>, stop_capturing),
+        );
+}
+
+fn action_label(action: Action) -> &'static str {
+    match action {
+        Action::MoveLeft => "Move left: ",
+        Action::MoveRight => "Move right: ",
+        Action::Pause => "Pause: ",
+        Action::Rewind => "Rewind: ",
+        Action::Dash => "Dash: ",
+        Action::Mute => "Mute: ",
+        Action::Defuse => "Defuse bomb: ",
+    }
+}
+
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{key:?}");
+    ["Key", "Digit", "Arrow"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .filter(|rest| !rest.is_empty())
+        .map_or_else(|| name.clone(), str::to_string)
+}
+
+fn binding_value(bindings: &InputBindings, capturing: &Capturing, action: Action) -> String {
+    if capturing.0 == Some(action) {
+        return "Press a key...".to_string();
+    }
+    let keys = bindings.keys(action);
+    if keys.is_empty() {
+        return "Unbound".to_string();
+    }
+    keys.iter()
+        .map(|&key| key_name(key))
+        .collect::<Vec<_>>()
+        .join(" / ")
+}
+
+fn setup(mut commands: Commands, bindings: Res<InputBindings>, capturing: Res<Capturing>) {
+    let button_style = Style {
+        width: Val::Px(500.0),
+        height: Val::Px(48.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font_size: 36.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.05, 0.05, 0.05, 0.95).into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            OnControlsMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Key bindings",
+                        TextStyle {
+                            font_size: 60.,
+                            ..default()
+                        },
+                    ));
+
+                    for action in ACTIONS {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::Rebind(action),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle::from_sections([
+                                        TextSection::new(
+                                            action_label(action),
+                                            button_text_style.clone(),
+                                        ),
+                                        TextSection::new(
+                                            binding_value(&bindings, &capturing, action),
+                                            button_text_style.clone(),
+                                        ),
+                                    ]),
+                                    BindingText(action),
+                                ));
+                            });
+                    }
+
+                    for (action, label) in [
+                        (MenuButtonAction::Reset, "Reset to defaults"),
+                        (MenuButtonAction::Back, "Back"),
+                    ] {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                action,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    label,
+                                    button_text_style.clone(),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+fn stop_capturing(mut capturing: ResMut<Capturing>) {
+    capturing.0 = None;
+}
+
+// Escape always cancels a capture, so it can't be bound from here; reset
+// restores it for pausing.
+fn capture_key(
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+    mut capturing: ResMut<Capturing>,
+    mut bindings: ResMut<InputBindings>,
+    dir: Res<DataDir>,
+) {
+    let Some(action) = capturing.0 else {
+        return;
+    };
+    let Some(&key) = keyboard_input.get_just_pressed().next() else {
+        return;
+    };
+    if key != KeyCode::Escape {
+        bindings.rebind(action, key);
+        store_bindings(&dir, &bindings);
+    }
+    capturing.0 = None;
+    // The captured press shouldn't also leave this screen or reach the game.
+    keyboard_input.reset_all();
+}
+
+fn keyboard_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut settings_state: ResMut<NextState<SettingsMenu>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        settings_state.set(SettingsMenu::Open);
+    }
+}
+
+fn update_labels(
+    bindings: Res<InputBindings>,
+    capturing: Res<Capturing>,
+    mut binding_text_query: Query<(&mut Text, &BindingText)>,
+) {
+    if !bindings.is_changed() && !capturing.is_changed() {
+        return;
+    }
+    for (mut text, BindingText(action)) in &mut binding_text_query {
+        text.sections[1].value = binding_value(&bindings, &capturing, *action);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut capturing: ResMut<Capturing>,
+    mut bindings: ResMut<InputBindings>,
+    mut settings_state: ResMut<NextState<SettingsMenu>>,
+    dir: Res<DataDir>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Rebind(action) => {
+                    capturing.0 = Some(*action);
+                }
+                MenuButtonAction::Reset => {
+                    capturing.0 = None;
+                    *bindings = InputBindings::default();
+                    store_bindings(&dir, &bindings);
+                }
+                MenuButtonAction::Back => {
+                    settings_state.set(SettingsMenu::Open);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::achievements::Achievement;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum BasketSkin {
+    #[default]
+    Classic,
+    Golden,
+    Ruby,
+}
+
+impl BasketSkin {
+    pub const ALL: [BasketSkin; 3] = [BasketSkin::Classic, BasketSkin::Golden, BasketSkin::Ruby];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BasketSkin::Classic => "Classic basket",
+            BasketSkin::Golden => "Golden basket",
+            BasketSkin::Ruby => "Ruby basket",
+        }
+    }
+
+    pub fn tint(self) -> Color {
+        match self {
+            BasketSkin::Classic => Color::WHITE,
+            BasketSkin::Golden => Color::srgb(1., 0.85, 0.3),
+            BasketSkin::Ruby => Color::srgb(1., 0.45, 0.45),
+        }
+    }
+
+    pub fn hitbox(self) -> (Vec2, Vec2) {
+        match self {
+            BasketSkin::Classic | BasketSkin::Golden | BasketSkin::Ruby => {
+                (Vec2::new(52., 26.), Vec2::new(0., -6.))
+            }
+        }
+    }
+
+    pub fn unlocked_by(self) -> Option<Achievement> {
+        match self {
+            BasketSkin::Classic => None,
+            BasketSkin::Golden => Some(Achievement::ComboTwenty),
+            BasketSkin::Ruby => Some(Achievement::HundredCatches),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum AppleTheme {
+    #[default]
+    Classic,
+    Emerald,
+}
+
+impl AppleTheme {
+    pub const ALL: [AppleTheme; 2] = [AppleTheme::Classic, AppleTheme::Emerald];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            AppleTheme::Classic => "Classic apples",
+            AppleTheme::Emerald => "Emerald apples",
+        }
+    }
+
+    pub fn tint(self) -> Color {
+        match self {
+            AppleTheme::Classic => Color::WHITE,
+            AppleTheme::Emerald => Color::srgb(0.5, 1., 0.5),
+        }
+    }
+
+    pub fn unlocked_by(self) -> Option<Achievement> {
+        match self {
+            AppleTheme::Classic => None,
+            AppleTheme::Emerald => Some(Achievement::TimeTrialFinished),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+
+use crate::{despawn_screen, pause_menu::toggle_pause, GameState, PauseMode};
+
+const COUNTDOWN_SECONDS: f32 = 3.;
+const GO_DURATION: f32 = 0.5;
+
+#[derive(Component)]
+struct OnCountdownScreen(Timer);
+
+#[derive(Component)]
+struct GoBanner(Timer);
+
+pub fn countdown_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (start_countdown, tick_countdown)
+            .chain()
+            // Pausing on the countdown's last frame has to win.
+            .before(toggle_pause)
+            .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Countdown))),
+    )
+    .add_systems(Update, fade_go_banner.run_if(in_state(GameState::Game)))
+    .add_systems(
+        OnExit(PauseMode::Countdown),
+        despawn_screen::<OnCountdownScreen>,
+    )
+    .add_systems(OnEnter(GameState::Game), check_fresh_run)
+    .add_systems(OnExit(GameState::Game), despawn_screen::<GoBanner>);
+}
+
+// `PauseMode` is created after `GameState` changes but before its enter
+// schedules run, so a run always opens counting down.
+fn check_fresh_run(pause_mode: Option<Res<State<PauseMode>>>) {
+    debug_assert_eq!(
+        pause_mode.map(|mode| mode.get().clone()),
+        Some(PauseMode::Countdown),
+        "a run started without a fresh PauseMode"
+    );
+}
+
+fn spawn_banner(commands: &mut Commands, text: &str, marker: impl Component) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            marker,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                text,
+                TextStyle {
+                    font_size: 120.,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn start_countdown(mut commands: Commands, countdown_query: Query<(), With<OnCountdownScreen>>) {
+    if !countdown_query.is_empty() {
+        return;
+    }
+    spawn_banner(
+        &mut commands,
+        &COUNTDOWN_SECONDS.to_string(),
+        OnCountdownScreen(Timer::from_seconds(COUNTDOWN_SECONDS, TimerMode::Once)),
+    );
+}
+
+fn tick_countdown(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pause_mode: ResMut<NextState<PauseMode>>,
+    mut countdown_query: Query<(Entity, &mut OnCountdownScreen, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (entity, mut countdown, children) in &mut countdown_query {
+        countdown.0.tick(time.delta());
+        if countdown.0.finished() {
+            commands.entity(entity).despawn_recursive();
+            pause_mode.set(PauseMode::Playing);
+            spawn_banner(
+                &mut commands,
+                "Go!",
+                GoBanner(Timer::from_seconds(GO_DURATION, TimerMode::Once)),
+            );
+            continue;
+        }
+        let remaining = countdown.0.remaining_secs().ceil().to_string();
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                text.sections[0].value.clone_from(&remaining);
+            }
+        }
+    }
+}
+
+fn fade_go_banner(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut banner_query: Query<(Entity, &mut GoBanner, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (entity, mut banner, children) in &mut banner_query {
+        banner.0.tick(time.delta());
+        if banner.0.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let alpha = banner.0.fraction_remaining();
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                for section in &mut text.sections {
+                    section.style.color.set_alpha(alpha);
+                }
+            }
+        }
+    }
+}
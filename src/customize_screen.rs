@@ -0,0 +1,311 @@
+use bevy::prelude::*;
+
+use crate::{
+    achievements::Achievement,
+    cosmetics::{AppleTheme, BasketSkin},
+    despawn_screen,
+    persistence::Profile,
+    GameState, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+const LOCKED_ITEM: Color = Color::srgb(0.07, 0.07, 0.07);
+const SELECTED_TEXT: Color = Color::srgb(1., 0.85, 0.2);
+
+#[derive(Component)]
+struct OnCustomizeScreen;
+
+#[derive(Component)]
+enum MenuButtonAction {
+    Basket(BasketSkin),
+    Apple(AppleTheme),
+    BuyMagnet,
+    Back,
+}
+
+pub fn customize_screen_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Customize), setup)
+        .add_systems(
+            Update,
+            (button_system, menu_action, refresh_on_profile_change)
+                .run_if(in_state(GameState::Customize)),
+        )
+        .add_systems(
+            OnExit(GameState::Customize),
+            despawn_screen::<OnCustomizeScreen>,
+        );
+}
+
+fn setup(mut commands: Commands, profile: Res<Profile>) {
+    spawn_screen(&mut commands, &profile);
+}
+
+fn refresh_on_profile_change(
+    mut commands: Commands,
+    profile: Res<Profile>,
+    screen_query: Query<Entity, With<OnCustomizeScreen>>,
+) {
+    if !profile.is_changed() {
+        return;
+    }
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_screen(&mut commands, &profile);
+}
+
+fn spawn_item(
+    parent: &mut ChildBuilder,
+    name: &str,
+    selected: bool,
+    unlocked_by: Option<Achievement>,
+    unlocked: bool,
+    action: MenuButtonAction,
+) {
+    let item_style = Style {
+        width: Val::Px(300.0),
+        height: Val::Px(50.0),
+        margin: UiRect::all(Val::Px(8.0)),
+        flex_direction: FlexDirection::Column,
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let item_text_style = TextStyle {
+        font_size: 28.0,
+        color: if selected {
+            SELECTED_TEXT
+        } else {
+            Color::WHITE
+        },
+        ..default()
+    };
+
+    if unlocked {
+        parent
+            .spawn((
+                ButtonBundle {
+                    style: item_style,
+                    background_color: NORMAL_BUTTON.into(),
+                    ..default()
+                },
+                action,
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(name, item_text_style));
+            });
+    } else {
+        parent
+            .spawn(NodeBundle {
+                style: item_style,
+                background_color: LOCKED_ITEM.into(),
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    name,
+                    TextStyle {
+                        color: Color::srgb(0.4, 0.4, 0.4),
+                        ..item_text_style
+                    },
+                ));
+                if let Some(achievement) = unlocked_by {
+                    parent.spawn(TextBundle::from_section(
+                        achievement.description(),
+                        TextStyle {
+                            font_size: 16.,
+                            color: Color::srgb(0.6, 0.6, 0.6),
+                            ..default()
+                        },
+                    ));
+                }
+            });
+    }
+}
+
+fn spawn_magnet_upgrade(parent: &mut ChildBuilder, profile: &Profile) {
+    let name = match profile.next_magnet_cost() {
+        Some(cost) => format!("Magnet aura {}: {cost} catches", profile.magnet_tier + 1),
+        None => "Magnet aura maxed".to_string(),
+    };
+    let affordable = profile
+        .next_magnet_cost()
+        .is_some_and(|cost| profile.lifetime_catches >= cost);
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            spawn_item(
+                parent,
+                &name,
+                false,
+                None,
+                affordable,
+                MenuButtonAction::BuyMagnet,
+            );
+            parent.spawn(TextBundle::from_section(
+                format!("Lifetime catches: {}", profile.lifetime_catches),
+                TextStyle {
+                    font_size: 16.,
+                    color: Color::srgb(0.6, 0.6, 0.6),
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn spawn_screen(commands: &mut Commands, profile: &Profile) {
+    let column_style = Style {
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnCustomizeScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: column_style.clone(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Customize",
+                        TextStyle {
+                            font_size: 60.,
+                            ..default()
+                        },
+                    ));
+
+                    parent.spawn(NodeBundle::default()).with_children(|parent| {
+                        parent
+                            .spawn(NodeBundle {
+                                style: column_style.clone(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                for skin in BasketSkin::ALL {
+                                    spawn_item(
+                                        parent,
+                                        skin.name(),
+                                        profile.active_basket_skin() == skin,
+                                        skin.unlocked_by(),
+                                        profile.is_unlocked(skin.unlocked_by()),
+                                        MenuButtonAction::Basket(skin),
+                                    );
+                                }
+                            });
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: column_style.clone(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                for theme in AppleTheme::ALL {
+                                    spawn_item(
+                                        parent,
+                                        theme.name(),
+                                        profile.active_apple_theme() == theme,
+                                        theme.unlocked_by(),
+                                        profile.is_unlocked(theme.unlocked_by()),
+                                        MenuButtonAction::Apple(theme),
+                                    );
+                                }
+                            });
+                    });
+
+                    spawn_magnet_upgrade(parent, profile);
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: Style {
+                                    width: Val::Px(250.0),
+                                    height: Val::Px(65.0),
+                                    margin: UiRect::all(Val::Px(20.0)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Back,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Back",
+                                TextStyle {
+                                    font_size: 40.0,
+                                    ..default()
+                                },
+                            ));
+                        });
+                });
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut profile: ResMut<Profile>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Basket(skin) => {
+                    profile.basket_skin = *skin;
+                }
+                MenuButtonAction::Apple(theme) => {
+                    profile.apple_theme = *theme;
+                }
+                MenuButtonAction::BuyMagnet => {
+                    profile.buy_magnet_tier();
+                }
+                MenuButtonAction::Back => {
+                    game_state.set(GameState::MainMenu);
+                }
+            }
+        }
+    }
+}
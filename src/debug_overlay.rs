@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin},
+    prelude::*,
+    time::common_conditions::on_timer,
+};
+
+use crate::{game::Apple, GameState, PauseMode, Scoreboard};
+
+// Refreshing every frame would rebuild the text layout for no benefit.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Component)]
+struct DebugOverlay;
+
+pub fn debug_overlay_plugin(app: &mut App) {
+    app.add_plugins(FrameTimeDiagnosticsPlugin).add_systems(
+        Update,
+        (
+            toggle_overlay,
+            update_overlay.run_if(on_timer(REFRESH_INTERVAL).or_else(overlay_added)),
+        )
+            .chain(),
+    );
+}
+
+fn toggle_overlay(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    overlay_query: Query<Entity, With<DebugOverlay>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F3) {
+        return;
+    }
+    if let Ok(overlay) = overlay_query.get_single() {
+        commands.entity(overlay).despawn_recursive();
+        return;
+    }
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 18.,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(40.),
+            right: Val::Px(10.),
+            padding: UiRect::all(Val::Px(6.)),
+            ..default()
+        })
+        .with_background_color(Color::srgba(0., 0., 0., 0.6)),
+        ZIndex::Global(20),
+        DebugOverlay,
+    ));
+}
+
+fn overlay_added(overlay_query: Query<(), Added<DebugOverlay>>) -> bool {
+    !overlay_query.is_empty()
+}
+
+fn update_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    apple_query: Query<(), With<Apple>>,
+    scoreboard: Option<Res<Scoreboard>>,
+    game_state: Res<State<GameState>>,
+    pause_mode: Option<Res<State<PauseMode>>>,
+    mut overlay_query: Query<&mut Text, With<DebugOverlay>>,
+) {
+    let Ok(mut text) = overlay_query.get_single_mut() else {
+        return;
+    };
+    let smoothed = |path| {
+        diagnostics
+            .get(path)
+            .and_then(|diagnostic| diagnostic.smoothed())
+            .unwrap_or(0.)
+    };
+    let score = scoreboard.map_or(0, |scoreboard| scoreboard.score);
+    let state = match pause_mode {
+        Some(pause_mode) => format!("{:?} / {:?}", game_state.get(), pause_mode.get()),
+        None => format!("{:?}", game_state.get()),
+    };
+    text.sections[0].value = format!(
+        "FPS: {:.0}\nFrame: {:.1} ms\nApples: {}\nScore: {score}\nState: {state}",
+        smoothed(&FrameTimeDiagnosticsPlugin::FPS),
+        smoothed(&FrameTimeDiagnosticsPlugin::FRAME_TIME),
+        apple_query.iter().count(),
+    );
+}
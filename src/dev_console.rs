@@ -0,0 +1,431 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    ecs::system::SystemState,
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState, InputSystem,
+    },
+    prelude::*,
+};
+use rand::Rng;
+
+use crate::{
+    actions::ReadActions,
+    cheats::Cheats,
+    game::{spawn_apple, spawn_x_range, spawn_y, AppleKind},
+    persistence::Profile,
+    replay::{InputSource, Replay},
+    start_run,
+    textures::{Texture, Textures},
+    Difficulty, DifficultyCurve, GameConfig, GameMode, GameRng, GameState, PlayArea, PowerUp,
+    Scoreboard,
+};
+
+const SCROLLBACK_LEN: usize = 100;
+const VISIBLE_LINES: usize = 12;
+const MAX_SPAWN_COUNT: u32 = 50;
+const ERROR_COLOR: Color = Color::srgb(1., 0.4, 0.4);
+const OUTPUT_COLOR: Color = Color::srgb(0.85, 0.85, 0.85);
+const ECHO_COLOR: Color = Color::srgb(0.6, 0.6, 0.6);
+
+type CommandResult = Result<String, String>;
+
+struct ConsoleCommand {
+    name: &'static str,
+    usage: &'static str,
+    run: fn(&mut World, &[&str]) -> CommandResult,
+}
+
+const COMMANDS: [ConsoleCommand; 6] = [
+    ConsoleCommand {
+        name: "help",
+        usage: "help",
+        run: help,
+    },
+    ConsoleCommand {
+        name: "give_score",
+        usage: "give_score <amount>",
+        run: give_score,
+    },
+    ConsoleCommand {
+        name: "spawn",
+        usage: "spawn <apple|golden|rotten|rewind|widen|slow|star> [count]",
+        run: spawn,
+    },
+    ConsoleCommand {
+        name: "set",
+        usage: "set spawn_interval <seconds>",
+        run: set,
+    },
+    ConsoleCommand {
+        name: "state",
+        usage: "state <main_menu|game|game_over|results|stats|customize>",
+        run: state,
+    },
+    ConsoleCommand {
+        name: "seed",
+        usage: "seed <number>",
+        run: seed,
+    },
+];
+
+#[derive(Resource, Default)]
+struct Console {
+    open: bool,
+    input: String,
+    scrollback: VecDeque<(String, Color)>,
+    pending: Vec<String>,
+}
+
+impl Console {
+    fn push_line(&mut self, line: String, color: Color) {
+        if self.scrollback.len() == SCROLLBACK_LEN {
+            self.scrollback.pop_front();
+        }
+        self.scrollback.push_back((line, color));
+    }
+
+    fn complete(&mut self) {
+        if self.input.contains(' ') {
+            return;
+        }
+        let matches: Vec<_> = COMMANDS
+            .iter()
+            .map(|command| command.name)
+            .filter(|name| name.starts_with(&self.input))
+            .collect();
+        match matches.as_slice() {
+            [] => {}
+            [name] => self.input = format!("{name} "),
+            names => {
+                let mut prefix = names[0].to_string();
+                for name in &names[1..] {
+                    while !name.starts_with(&prefix) {
+                        prefix.pop();
+                    }
+                }
+                self.input = prefix;
+                self.push_line(names.join("  "), OUTPUT_COLOR);
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ScrollbackText;
+
+#[derive(Component)]
+struct InputText;
+
+pub fn dev_console_plugin(app: &mut App) {
+    app.init_resource::<Console>()
+        .add_systems(Startup, spawn_console)
+        .add_systems(
+            PreUpdate,
+            console_input.after(InputSystem).before(ReadActions),
+        )
+        .add_systems(Update, (run_pending_commands, update_console).chain());
+}
+
+fn spawn_console(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.),
+                    width: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.)),
+                    row_gap: Val::Px(4.),
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.8).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            ConsoleRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((TextBundle::default(), ScrollbackText));
+            parent.spawn((
+                TextBundle::from_section(
+                    "> ",
+                    TextStyle {
+                        font_size: 18.,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ),
+                InputText,
+            ));
+        });
+}
+
+fn console_input(
+    mut console: ResMut<Console>,
+    mut keyboard_events: ResMut<Events<KeyboardInput>>,
+    mut keyboard_input: ResMut<ButtonInput<KeyCode>>,
+) {
+    let toggled = keyboard_input.just_pressed(KeyCode::Backquote);
+    if toggled {
+        console.open = !console.open;
+    }
+    if !console.open {
+        if toggled {
+            keyboard_events.clear();
+            keyboard_input.reset_all();
+        }
+        return;
+    }
+
+    // Drained rather than read, so cheat codes and other readers of the
+    // events never see what is typed here.
+    for event in keyboard_events.drain() {
+        if event.state != ButtonState::Pressed || event.key_code == KeyCode::Backquote {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => console.input.push_str(text),
+            Key::Space => console.input.push(' '),
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Tab => console.complete(),
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                if !line.trim().is_empty() {
+                    console.pending.push(line);
+                }
+            }
+            _ => {}
+        }
+    }
+    // Keep gameplay and menus from reacting to what is typed.
+    keyboard_input.reset_all();
+}
+
+fn run_pending_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<Console>().pending);
+    for line in pending {
+        let result = execute(world, &line);
+        let mut console = world.resource_mut::<Console>();
+        console.push_line(format!("> {line}"), ECHO_COLOR);
+        match result {
+            Ok(output) => console.push_line(output, OUTPUT_COLOR),
+            Err(error) => console.push_line(error, ERROR_COLOR),
+        }
+    }
+}
+
+fn execute(world: &mut World, line: &str) -> CommandResult {
+    let mut words = line.split_whitespace();
+    let name = words.next().unwrap_or_default();
+    let args: Vec<_> = words.collect();
+    let command = COMMANDS
+        .iter()
+        .find(|command| command.name == name)
+        .ok_or_else(|| format!("Unknown command {name:?}, try help"))?;
+    (command.run)(world, &args).map_err(|error| format!("{error} (usage: {})", command.usage))
+}
+
+fn update_console(
+    console: Res<Console>,
+    mut root_query: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut scrollback_query: Query<&mut Text, (With<ScrollbackText>, Without<InputText>)>,
+    mut input_query: Query<&mut Text, With<InputText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    for mut visibility in &mut root_query {
+        *visibility = if console.open {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+    }
+    for mut text in &mut scrollback_query {
+        let skip = console.scrollback.len().saturating_sub(VISIBLE_LINES);
+        text.sections = console
+            .scrollback
+            .iter()
+            .skip(skip)
+            .map(|(line, color)| {
+                TextSection::new(
+                    format!("{line}\n"),
+                    TextStyle {
+                        font_size: 18.,
+                        color: *color,
+                        ..default()
+                    },
+                )
+            })
+            .collect();
+    }
+    for mut text in &mut input_query {
+        text.sections[0].value = format!("> {}_", console.input);
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(args: &[&str], index: usize, name: &str) -> Result<T, String> {
+    let arg = args.get(index).ok_or_else(|| format!("Missing {name}"))?;
+    arg.parse().map_err(|_| format!("Invalid {name} {arg:?}"))
+}
+
+fn require_run(world: &World) -> Result<(), String> {
+    match world.resource::<State<GameState>>().get() {
+        GameState::Game => Ok(()),
+        _ => Err("No run in progress".to_string()),
+    }
+}
+
+// Runs changed from the console don't count towards records or achievements,
+// and can't be played back.
+fn taint_run(world: &mut World) {
+    world.resource_mut::<Cheats>().console_used = true;
+    if !world.resource::<InputSource>().is_replay() {
+        world.resource_mut::<Replay>().altered = true;
+    }
+}
+
+fn help(_world: &mut World, _args: &[&str]) -> CommandResult {
+    Ok(COMMANDS
+        .iter()
+        .map(|command| command.usage)
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+fn give_score(world: &mut World, args: &[&str]) -> CommandResult {
+    let amount: i32 = parse_arg(args, 0, "amount")?;
+    require_run(world)?;
+    taint_run(world);
+    let mut scoreboard = world.resource_mut::<Scoreboard>();
+    scoreboard.score = scoreboard.score.saturating_add(amount).max(0);
+    Ok(format!("Score is now {}", scoreboard.score))
+}
+
+#[allow(clippy::type_complexity)]
+fn spawn(world: &mut World, args: &[&str]) -> CommandResult {
+    let (apple_kind, power_up) = match args.first().copied() {
+        Some("apple") => (AppleKind::Normal, None),
+        Some("golden") => (AppleKind::Golden, None),
+        Some("rotten") => (AppleKind::Rotten, None),
+        Some("rewind") => (AppleKind::Normal, Some(PowerUp::Rewind)),
+        Some("widen") => (AppleKind::Normal, Some(PowerUp::Widen)),
+        Some("slow") => (AppleKind::Normal, Some(PowerUp::SlowMotion)),
+        Some("star") => (AppleKind::Normal, Some(PowerUp::Star)),
+        Some(kind) => return Err(format!("Unknown kind {kind:?}")),
+        None => return Err("Missing kind".to_string()),
+    };
+    let count: u32 = if args.len() > 1 {
+        parse_arg(args, 1, "count")?
+    } else {
+        1
+    };
+    if count == 0 || count > MAX_SPAWN_COUNT {
+        return Err(format!("Count must be between 1 and {MAX_SPAWN_COUNT}"));
+    }
+    require_run(world)?;
+
+    let mut system_state: SystemState<(
+        Commands,
+        Textures,
+        Res<Profile>,
+        Res<GameConfig>,
+        ResMut<GameRng>,
+        Res<PlayArea>,
+    )> = SystemState::new(world);
+    let (mut commands, textures, profile, config, mut game_rng, play_area) =
+        system_state.get_mut(world);
+    let (_, texture_size) = textures
+        .sprite(Texture::Apple)
+        .ok_or_else(|| "Apple texture is not loaded".to_string())?;
+    let top = spawn_y(play_area.height, texture_size.y, config.apple_scale);
+    let range = spawn_x_range(play_area.width, texture_size.x, config.apple_scale);
+    for i in 0..count {
+        let x = game_rng.rng.gen_range(-range..=range);
+        // Stack extra apples above the first so they arrive one after another.
+        let y = top + i as f32 * texture_size.y * config.apple_scale;
+        spawn_apple(
+            &mut commands,
+            &textures,
+            &profile,
+            &config,
+            &mut game_rng.rng,
+            Vec3::new(x, y, 0.),
+            apple_kind,
+            power_up,
+        );
+    }
+    system_state.apply(world);
+    taint_run(world);
+    Ok(format!("Spawned {count}"))
+}
+
+fn set(world: &mut World, args: &[&str]) -> CommandResult {
+    match args.first().copied() {
+        Some("spawn_interval") => {
+            let seconds: f32 = parse_arg(args, 1, "seconds")?;
+            if !seconds.is_finite() || seconds <= 0. {
+                return Err("Interval must be positive".to_string());
+            }
+            require_run(world)?;
+            taint_run(world);
+            world.resource_mut::<DifficultyCurve>().pinned_interval = Some(seconds);
+            Ok(format!("Spawn interval pinned to {seconds}s"))
+        }
+        Some(variable) => Err(format!("Unknown variable {variable:?}")),
+        None => Err("Missing variable".to_string()),
+    }
+}
+
+fn state(world: &mut World, args: &[&str]) -> CommandResult {
+    let target = match args.first().copied() {
+        Some("main_menu") => GameState::MainMenu,
+        Some("game") => GameState::Game,
+        Some("game_over") => {
+            require_run(world)?;
+            GameState::GameOver
+        }
+        Some("results") => {
+            require_run(world)?;
+            GameState::Results
+        }
+        Some("stats") => GameState::Stats,
+        Some("customize") => GameState::Customize,
+        Some(name) => return Err(format!("Unknown state {name:?}")),
+        None => return Err("Missing state".to_string()),
+    };
+    if target == GameState::Game {
+        let game_mode = *world.resource::<GameMode>();
+        let difficulty = *world.resource::<Difficulty>();
+        let config = world.resource::<GameConfig>().clone();
+        let mut system_state: SystemState<Commands> = SystemState::new(world);
+        start_run(
+            &mut system_state.get_mut(world),
+            &config,
+            game_mode,
+            difficulty,
+        );
+        system_state.apply(world);
+    }
+    world
+        .resource_mut::<NextState<GameState>>()
+        .set(target.clone());
+    Ok(format!("Switching to {target:?}"))
+}
+
+fn seed(world: &mut World, args: &[&str]) -> CommandResult {
+    let seed: u64 = parse_arg(args, 0, "seed")?;
+    require_run(world)?;
+    taint_run(world);
+    world.insert_resource(GameRng::from_seed(seed));
+    Ok(format!("Run reseeded with {seed}"))
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use bevy::utils::Instant;
+use bevy::{
+    prelude::*,
+    time::TimeSystem,
+    winit::{UpdateMode, WinitSettings},
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::FrameLimit;
+use crate::{GameState, PauseMode};
+
+const MENU_FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / 30);
+#[cfg(not(target_arch = "wasm32"))]
+const SPIN_MARGIN: Duration = Duration::from_millis(1);
+
+pub fn frame_limiter_plugin(app: &mut App) {
+    // In a browser frames are paced by requestAnimationFrame, and the page
+    // can't block the thread to wait for the next one.
+    #[cfg(not(target_arch = "wasm32"))]
+    app.add_systems(Last, limit_frame_rate);
+    app.add_systems(
+        Update,
+        // Headless there is no event loop to throttle.
+        apply_update_mode
+            .run_if(resource_exists::<WinitSettings>)
+            .run_if(state_changed::<GameState>.or_else(state_changed::<PauseMode>)),
+    )
+    .add_systems(First, report_clamped_delta.after(TimeSystem));
+}
+
+fn report_clamped_delta(real_time: Res<Time<Real>>, virtual_time: Res<Time<Virtual>>) {
+    let requested = real_time
+        .delta()
+        .mul_f64(virtual_time.effective_speed_f64());
+    if requested > virtual_time.max_delta() {
+        info!(
+            "Clamped a {:.0} ms frame to {:.0} ms of game time",
+            requested.as_secs_f64() * 1000.,
+            virtual_time.max_delta().as_secs_f64() * 1000.
+        );
+    }
+}
+
+// `PauseMode` only exists while a run is in progress.
+fn apply_update_mode(
+    pause_mode: Option<Res<State<PauseMode>>>,
+    mut winit_settings: ResMut<WinitSettings>,
+) {
+    let mode = if pause_mode.is_some_and(|mode| *mode.get() != PauseMode::Paused) {
+        UpdateMode::Continuous
+    } else {
+        UpdateMode::reactive(MENU_FRAME_TIME)
+    };
+    winit_settings.focused_mode = mode;
+    winit_settings.unfocused_mode = mode;
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn limit_frame_rate(frame_limit: Res<FrameLimit>, mut frame_start: Local<Option<Instant>>) {
+    if let (Some(fps), Some(start)) = (frame_limit.fps_cap, *frame_start) {
+        let deadline = start + Duration::from_secs_f64(1. / fps as f64);
+        let now = Instant::now();
+        if deadline > now + SPIN_MARGIN {
+            std::thread::sleep(deadline - now - SPIN_MARGIN);
+        }
+        while Instant::now() < deadline {
+            std::hint::spin_loop();
+        }
+    }
+    *frame_start = Some(Instant::now());
+}
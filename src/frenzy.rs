@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::{
+    game::{GameTick, Player},
+    AppleCaughtEvent, AppleMissedEvent, AppleSpawnerConfig, GameState, PauseMode, RunStats,
+};
+
+const PERFECT_STREAK: u32 = 5;
+const FRENZY_DURATION: f32 = 6.;
+
+// Sent when a golden frenzy begins, for the announcer.
+#[derive(Event)]
+pub struct FrenzyStartedEvent;
+
+// A streak of perfect catches sets off a frenzy in which every apple is
+// golden. Frenzies never overlap: one earned during another starts when it ends.
+#[derive(Resource, Default)]
+struct Frenzy {
+    perfect_streak: u32,
+    timer: Option<Timer>,
+    deferred: bool,
+}
+
+pub fn frenzy_plugin(app: &mut App) {
+    app.add_event::<FrenzyStartedEvent>()
+        .add_systems(OnEnter(GameState::Game), setup)
+        .add_systems(
+            FixedUpdate,
+            (count_perfect_catches, update_frenzy)
+                .chain()
+                .after(GameTick)
+                .run_if(
+                    in_state(GameState::Game)
+                        .and_then(in_state(PauseMode::Playing))
+                        .and_then(any_with_component::<Player>),
+                ),
+        );
+}
+
+fn setup(mut commands: Commands) {
+    commands.insert_resource(Frenzy::default());
+}
+
+// Any other catch, rotten ones included, or a miss breaks the streak.
+fn count_perfect_catches(
+    mut caught_events: EventReader<AppleCaughtEvent>,
+    mut missed_events: EventReader<AppleMissedEvent>,
+    mut frenzy: ResMut<Frenzy>,
+) {
+    for event in caught_events.read() {
+        if event.perfect && event.points > 0 {
+            frenzy.perfect_streak += 1;
+        } else {
+            frenzy.perfect_streak = 0;
+        }
+        if frenzy.perfect_streak == PERFECT_STREAK {
+            frenzy.perfect_streak = 0;
+            frenzy.deferred = true;
+        }
+    }
+    if missed_events.read().count() > 0 {
+        frenzy.perfect_streak = 0;
+    }
+}
+
+fn update_frenzy(
+    time: Res<Time>,
+    mut frenzy: ResMut<Frenzy>,
+    mut spawner: ResMut<AppleSpawnerConfig>,
+    mut run_stats: ResMut<RunStats>,
+    mut started_events: EventWriter<FrenzyStartedEvent>,
+) {
+    if let Some(timer) = &mut frenzy.timer {
+        if timer.tick(time.delta()).finished() {
+            frenzy.timer = None;
+        }
+    }
+    if frenzy.timer.is_none() && frenzy.deferred {
+        frenzy.deferred = false;
+        frenzy.timer = Some(Timer::from_seconds(FRENZY_DURATION, TimerMode::Once));
+        let elapsed = run_stats.elapsed;
+        run_stats.frenzy_times.push(elapsed);
+        started_events.send(FrenzyStartedEvent);
+    }
+    spawner.frenzy = frenzy.timer.is_some();
+}
@@ -0,0 +1,1918 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::{
+    math::bounding::{Aabb2d, BoundingVolume, IntersectsVolume},
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+    transform::TransformSystem,
+};
+
+use crate::{
+    cheats::{Cheats, EXTRA_LIVES},
+    interpolation::PreviousTransform,
+    lit_bomb::LitBomb,
+    persistence::{HighScore, Profile},
+    replay::{InputSource, ReadTickInput, TickInput, TickSpawn},
+    toasts::HudCorner,
+    waves::WavePlayer,
+    weekly::ActiveMutators,
+    MotionPreferences, MovementStyle, PauseMode, PlayArea, ShowMagnetAura,
+};
+
+use super::{
+    despawn_screen,
+    textures::{Texture, Textures, APPLE_FRAMES},
+    AppleCaughtEvent, AppleMissedEvent, AppleSpawnerConfig, AssistSettings, ComboMilestoneEvent,
+    DifficultyCurve, GameConfig, GameMode, GameRng, GameState, Gravity, Level, Lives, Multiplier,
+    PowerUp, PowerUpCollectedEvent, RequestedDrop, RunStats, Scoreboard, TimeDilation,
+    COMBO_MILESTONES,
+};
+
+use rand::Rng;
+
+#[derive(Component)]
+pub struct OnGameScreen;
+
+// The fixed-step simulation of a run, fed by the tick's input.
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct GameTick;
+
+const SPAWN_MARGIN: f32 = 4.;
+const BIG_BASKET_SCALE: f32 = 1.5;
+// Kept well below the basket's own speed so the aura only nudges apples.
+const MAGNET_MAX_SPEED: f32 = 60.;
+const MAGNET_PULL: f32 = 1.5;
+const MAGNET_AURA_COLOR: Color = Color::srgba(0.6, 0.8, 1., 0.12);
+const CATCH_FLASH_DURATION: f32 = 0.08;
+const CATCH_FLASH_COLOR: Color = Color::linear_rgb(4., 4., 4.);
+const BONUS_CATCH_FLASH_COLOR: Color = Color::linear_rgb(4., 3., 0.5);
+const PENALTY_CATCH_FLASH_COLOR: Color = Color::linear_rgb(4., 0.5, 0.5);
+const CATCH_SQUASH_DURATION: f32 = 0.2;
+const CATCH_SQUASH_SCALE: Vec2 = Vec2::new(1.2, 0.8);
+// Radians per second, i.e. up to half a turn either way.
+const MAX_APPLE_SPIN: f32 = std::f32::consts::PI;
+const APPLE_FRAME_DURATION: f32 = 0.15;
+const SPAWN_TOAST_DURATION: f32 = 2.;
+const REWIND_PICKUP_CHANCE: f64 = 0.03;
+const WIDEN_PICKUP_CHANCE: f64 = 0.03;
+const SLOW_MOTION_PICKUP_CHANCE: f64 = 0.02;
+const STAR_PICKUP_CHANCE: f64 = 0.02;
+const COMBO_BONUS_INTERVAL: u32 = 5;
+const COMBO_PULSE_DURATION: f32 = 0.4;
+const COMBO_PULSE_COLOR: Color = Color::srgb(1., 0.85, 0.2);
+const LEVEL_BANNER_DURATION: f32 = 1.5;
+const SLOW_MOTION_DURATION: Duration = Duration::from_secs(8);
+const SLOW_MOTION_BAR_WIDTH: f32 = 120.;
+const WIDEN_SCALE: f32 = 1.5;
+const WIDEN_DURATION: f32 = 10.;
+// How quickly the basket grows or shrinks, in widths per second.
+const WIDEN_EASE_SPEED: f32 = 2.;
+pub const GOLDEN_APPLE_CHANCE: f64 = 0.1;
+pub const ROTTEN_APPLE_CHANCE: f64 = 0.15;
+pub const NORMAL_APPLE_POINTS: i32 = 1;
+pub const GOLDEN_APPLE_POINTS: i32 = 5;
+pub const ROTTEN_APPLE_POINTS: i32 = -3;
+const GOLDEN_FALL_SPEED_SCALE: f32 = 1.3;
+// Only used when a variant's texture couldn't be loaded and the plain apple is drawn instead.
+const GOLDEN_FALLBACK_TINT: Color = Color::srgb(1., 0.85, 0.2);
+const GOLDEN_FALLBACK_SCALE: f32 = 1.15;
+const ROTTEN_FALLBACK_TINT: Color = Color::srgb(0.45, 0.35, 0.15);
+// How far from the basket's centre a catch still counts as perfect, as a
+// fraction of its half-width.
+const PERFECT_CATCH_FRACTION: f32 = 0.2;
+const BOMB_CHANCE: f64 = 0.04;
+const BOMB_SCALE: f32 = 0.5;
+const CANOPY_Z: f32 = -0.5;
+// Where apples hang from the tree, as fractions of the play area's width
+// from its left edge and of the canopy's height from its top.
+const BRANCH_ANCHORS: [Vec2; 7] = [
+    Vec2::new(0.07, 0.7),
+    Vec2::new(0.21, 0.8),
+    Vec2::new(0.35, 0.65),
+    Vec2::new(0.5, 0.78),
+    Vec2::new(0.64, 0.68),
+    Vec2::new(0.79, 0.82),
+    Vec2::new(0.93, 0.72),
+];
+const DASH_DISTANCE: f32 = 180.;
+const DASH_DURATION: f32 = 0.15;
+const DASH_COOLDOWN: f32 = 2.;
+const DASH_BAR_COLOR: Color = Color::srgb(0.6, 0.85, 1.);
+const HANG_DURATION: f32 = 0.4;
+const HANG_WOBBLE_ANGLE: f32 = 0.2;
+// Full swings per second while an apple works itself loose.
+const HANG_WOBBLE_RATE: f32 = 5.;
+
+#[derive(Component)]
+pub struct Player;
+
+#[derive(Component)]
+pub struct Apple;
+
+#[derive(Component)]
+pub struct Bomb;
+
+// Anything that drops from the top and collides with the basket.
+#[derive(Component)]
+pub struct Falling;
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum AppleKind {
+    Normal,
+    Golden,
+    Rotten,
+}
+
+impl AppleKind {
+    pub fn points(self) -> i32 {
+        match self {
+            AppleKind::Normal => NORMAL_APPLE_POINTS,
+            AppleKind::Golden => GOLDEN_APPLE_POINTS,
+            AppleKind::Rotten => ROTTEN_APPLE_POINTS,
+        }
+    }
+
+    fn fall_speed_scale(self) -> f32 {
+        match self {
+            AppleKind::Normal | AppleKind::Rotten => 1.,
+            AppleKind::Golden => GOLDEN_FALL_SPEED_SCALE,
+        }
+    }
+
+    fn roll(rng: &mut impl Rng) -> AppleKind {
+        let roll: f64 = rng.gen();
+        if roll < GOLDEN_APPLE_CHANCE {
+            AppleKind::Golden
+        } else if roll < GOLDEN_APPLE_CHANCE + ROTTEN_APPLE_CHANCE {
+            AppleKind::Rotten
+        } else {
+            AppleKind::Normal
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct SpriteSize(pub Vec2);
+
+#[derive(Component)]
+struct Widen {
+    timer: Timer,
+    width: f32,
+}
+
+impl Default for Widen {
+    fn default() -> Self {
+        Widen {
+            timer: Timer::new(Duration::ZERO, TimerMode::Once),
+            width: 1.,
+        }
+    }
+}
+
+#[derive(Component, Default)]
+struct BasketVelocity(f32);
+
+#[derive(Component)]
+struct Dash {
+    timer: Timer,
+    // Counts down once the dash is over.
+    cooldown: Timer,
+    direction: f32,
+}
+
+// Both timers start out finished, so the basket can dash straight away.
+impl Default for Dash {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(DASH_DURATION, TimerMode::Once);
+        let mut cooldown = Timer::from_seconds(DASH_COOLDOWN, TimerMode::Once);
+        timer.tick(timer.duration());
+        cooldown.tick(cooldown.duration());
+        Self {
+            timer,
+            cooldown,
+            direction: 0.,
+        }
+    }
+}
+
+#[derive(Component)]
+struct Hitbox(Vec2, Vec2);
+
+#[derive(Resource, Default)]
+struct ShowHitboxes(bool);
+
+#[derive(Component)]
+struct PointsText;
+
+#[derive(Component)]
+struct LivesText;
+
+#[derive(Component)]
+struct HighScoreText;
+
+#[derive(Component)]
+struct ComboText {
+    shown: u32,
+    pulse: Timer,
+}
+
+#[derive(Component)]
+struct LevelBanner(Timer);
+
+#[derive(Component)]
+struct SlowMotionBar;
+
+#[derive(Component)]
+struct SlowMotionBarFill;
+
+#[derive(Component)]
+struct DashBar;
+
+#[derive(Component)]
+struct DashBarFill;
+
+#[derive(Component)]
+struct SpawnToast(Timer);
+
+#[derive(Component)]
+struct MagnetAura(f32);
+
+#[derive(Component)]
+struct FlashTimer {
+    timer: Timer,
+    original_color: Color,
+}
+
+// Squashes only the drawn sprite, so collisions and edge clamping keep
+// using the unsquashed `SpriteSize`.
+#[derive(Component)]
+struct CatchSquash(Timer);
+
+#[derive(Component)]
+struct AngularVelocity(f32);
+
+// Rolled once when an apple or bomb appears, so effects can change a single
+// apple's fall without touching the movement system.
+#[derive(Component)]
+pub struct Velocity(pub Vec2);
+
+// Scales gravity and the terminal speed for a single apple.
+#[derive(Component)]
+pub struct Weight(pub f32);
+
+#[derive(Component)]
+struct AppleAnimation(Timer);
+
+// The unscaled texture size, which the tree is stretched from to span the
+// play area.
+#[derive(Component)]
+struct Canopy(Vec2);
+
+// Apples wobble on their branch for a moment before they drop.
+#[derive(Component)]
+pub struct Hanging(pub Timer);
+
+pub fn game_plugin(app: &mut App) {
+    app.add_event::<ComboMilestoneEvent>()
+        .add_event::<AppleCaughtEvent>()
+        .add_event::<AppleMissedEvent>()
+        .add_event::<PowerUpCollectedEvent>()
+        .init_resource::<ShowHitboxes>()
+        .add_systems(OnEnter(GameState::Game), (apply_cheats, setup).chain())
+        .add_systems(OnEnter(GameState::MainMenu), reset_combo)
+        .add_systems(
+            OnExit(PauseMode::Paused),
+            apply_basket_scale.run_if(in_state(GameState::Game)),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                update_widen,
+                update_time_dilation,
+                update_multiplier,
+                player_movement,
+                advance_difficulty,
+                release_apples,
+                assign_fall_velocity,
+                apply_gravity,
+                apply_velocity,
+                apple_movement,
+                apple_catching,
+                score_misses,
+                score_catches,
+                apple_spawning,
+            )
+                .chain()
+                .in_set(GameTick)
+                .after(ReadTickInput)
+                // Everything here assumes the basket is around.
+                .run_if(
+                    in_state(GameState::Game)
+                        .and_then(in_state(PauseMode::Playing))
+                        .and_then(any_with_component::<Player>),
+                ),
+        )
+        .add_systems(
+            Update,
+            (
+                (start_catch_flash, update_catch_flash).chain(),
+                (start_catch_squash, update_catch_squash).chain(),
+                spin_apples,
+                animate_apples,
+                update_spawn_toasts,
+                update_points_text,
+                update_multiplier_text,
+                update_combo_text,
+                (show_level_banner, fade_level_banner).chain(),
+                update_lives_text,
+                update_slow_motion_bar,
+                update_dash_bar,
+            )
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        )
+        .add_systems(
+            Update,
+            (
+                toggle_hitboxes,
+                // Gizmos need the renderer, so they are left alone until asked for.
+                draw_hitboxes.run_if(|show_hitboxes: Res<ShowHitboxes>| show_hitboxes.0),
+                show_magnet_aura.run_if(resource_changed::<ShowMagnetAura>),
+            )
+                .run_if(in_state(GameState::Game)),
+        )
+        .add_systems(
+            Update,
+            (apply_basket_scale, fit_to_play_area, fit_canopy)
+                .chain()
+                .run_if(in_state(GameState::Game).and_then(resource_changed::<PlayArea>)),
+        )
+        .add_systems(
+            PostUpdate,
+            follow_basket
+                .before(TransformSystem::TransformPropagate)
+                .run_if(in_state(GameState::Game)),
+        )
+        .add_systems(OnExit(GameState::Game), despawn_screen::<OnGameScreen>);
+}
+
+// Cheats typed on the main menu take effect as each run starts.
+fn apply_cheats(
+    cheats: Res<Cheats>,
+    game_mode: Res<GameMode>,
+    mut lives: ResMut<Lives>,
+    mut spawner: ResMut<AppleSpawnerConfig>,
+) {
+    if cheats.extra_lives && game_mode.has_lives() {
+        lives.0 += EXTRA_LIVES;
+    }
+    spawner.gold_rush = cheats.gold_rush;
+}
+
+#[allow(clippy::too_many_arguments)]
+fn setup(
+    mut commands: Commands,
+    textures: Textures,
+    play_area: Res<PlayArea>,
+    profile: Res<Profile>,
+    assist_settings: Res<AssistSettings>,
+    cheats: Res<Cheats>,
+    show_magnet_aura: Res<ShowMagnetAura>,
+    game_mode: Res<GameMode>,
+    lives: Res<Lives>,
+    high_score: Res<HighScore>,
+    config: Res<GameConfig>,
+    input_source: Res<InputSource>,
+    corner_query: Query<Entity, With<HudCorner>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    if config.apple_tree {
+        if let Some((texture, texture_size)) = textures.sprite(Texture::Canopy) {
+            commands.spawn((
+                SpriteBundle {
+                    transform: canopy_transform(&play_area, texture_size),
+                    texture,
+                    sprite: Sprite {
+                        custom_size: Some(texture_size),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Canopy(texture_size),
+                OnGameScreen,
+            ));
+        }
+    }
+    let loadout = input_source.loadout(&profile);
+    if let Some(radius) = loadout.magnet_radius() {
+        commands.spawn((
+            MaterialMesh2dBundle {
+                mesh: Mesh2dHandle(meshes.add(Circle::new(1.))),
+                material: materials.add(MAGNET_AURA_COLOR),
+                visibility: if show_magnet_aura.0 {
+                    Visibility::Inherited
+                } else {
+                    Visibility::Hidden
+                },
+                ..default()
+            },
+            MagnetAura(radius),
+            OnGameScreen,
+        ));
+    }
+    {
+        let (texture_handle, texture_size) = textures.sprite(Texture::Player).unwrap();
+        let scale = basket_scale(&assist_settings, &cheats);
+        let translation = Vec3::new(0., basket_y(play_area.height, texture_size.y, scale), 1.0);
+        let (half_extents, offset) = loadout.basket_skin.hitbox();
+        commands
+            .spawn(SpriteBundle {
+                transform: Transform {
+                    translation,
+                    scale: Vec3::new(scale, scale, 1.),
+                    ..default()
+                },
+                texture: texture_handle,
+                sprite: Sprite {
+                    color: loadout.basket_skin.tint(),
+                    custom_size: Some(texture_size),
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(Player)
+            .insert(PreviousTransform::new(translation))
+            .insert(SpriteSize(texture_size))
+            .insert(Hitbox(half_extents, offset))
+            .insert(Widen::default())
+            .insert(BasketVelocity::default())
+            .insert(Dash::default())
+            .insert(OnGameScreen);
+    }
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "Points: ",
+                TextStyle {
+                    font_size: 30.,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "0",
+                TextStyle {
+                    font_size: 30.,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                "",
+                TextStyle {
+                    font_size: 30.,
+                    color: PowerUp::Star.tint(),
+                    ..default()
+                },
+            ),
+        ]),
+        PointsText,
+        OnGameScreen,
+    ));
+    if *game_mode == GameMode::Endless {
+        commands.spawn((
+            TextBundle::from_section(
+                format!("Best: {}", high_score.score),
+                TextStyle {
+                    font_size: 30.,
+                    color: Color::srgb(1., 0.85, 0.2),
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.),
+                left: Val::Px(250.),
+                ..default()
+            }),
+            HighScoreText,
+            OnGameScreen,
+        ));
+    }
+    if game_mode.has_lives() {
+        commands.spawn((
+            TextBundle::from_section(
+                lives_label(lives.0),
+                TextStyle {
+                    font_size: 30.,
+                    color: Color::srgb(1., 0.4, 0.4),
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.),
+                left: Val::Px(460.),
+                ..default()
+            }),
+            LivesText,
+            OnGameScreen,
+        ));
+    }
+    let mut pulse = Timer::from_seconds(COMBO_PULSE_DURATION, TimerMode::Once);
+    pulse.set_elapsed(pulse.duration());
+    let combo_text = commands
+        .spawn((
+            TextBundle::from_section(
+                "Combo: 0",
+                TextStyle {
+                    font_size: 26.,
+                    ..default()
+                },
+            ),
+            ComboText { shown: 0, pulse },
+            OnGameScreen,
+        ))
+        .id();
+    if let Ok(corner) = corner_query.get_single() {
+        commands.entity(corner).add_child(combo_text);
+    }
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(40.),
+                    left: Val::Px(10.),
+                    width: Val::Px(SLOW_MOTION_BAR_WIDTH),
+                    height: Val::Px(8.),
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.5).into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            SlowMotionBar,
+            OnGameScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    background_color: PowerUp::SlowMotion.tint().into(),
+                    ..default()
+                },
+                SlowMotionBarFill,
+            ));
+        });
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(52.),
+                    left: Val::Px(10.),
+                    width: Val::Px(SLOW_MOTION_BAR_WIDTH),
+                    height: Val::Px(6.),
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.5).into(),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            DashBar,
+            OnGameScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                NodeBundle {
+                    style: Style {
+                        width: Val::Percent(0.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    background_color: DASH_BAR_COLOR.into(),
+                    ..default()
+                },
+                DashBarFill,
+            ));
+        });
+}
+
+fn lives_label(lives: u32) -> String {
+    format!("Lives: {lives}")
+}
+
+fn update_lives_text(lives: Res<Lives>, mut lives_text_query: Query<&mut Text, With<LivesText>>) {
+    if !lives.is_changed() {
+        return;
+    }
+    for mut lives_text in &mut lives_text_query {
+        lives_text.sections[0].value = lives_label(lives.0);
+    }
+}
+
+fn update_slow_motion_bar(
+    time_dilation: Res<TimeDilation>,
+    mut bar_query: Query<&mut Visibility, With<SlowMotionBar>>,
+    mut fill_query: Query<&mut Style, With<SlowMotionBarFill>>,
+) {
+    for mut visibility in &mut bar_query {
+        *visibility = if time_dilation.is_active() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    for mut style in &mut fill_query {
+        style.width = Val::Percent(100. * time_dilation.timer.fraction_remaining());
+    }
+}
+
+// Fills back up while the dash recharges, and hides once it is ready.
+fn update_dash_bar(
+    dash_query: Query<&Dash, With<Player>>,
+    mut bar_query: Query<&mut Visibility, With<DashBar>>,
+    mut fill_query: Query<&mut Style, With<DashBarFill>>,
+) {
+    let Ok(dash) = dash_query.get_single() else {
+        return;
+    };
+    let recharging = dash.timer.finished() && !dash.cooldown.finished();
+    for mut visibility in &mut bar_query {
+        *visibility = if recharging {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    for mut style in &mut fill_query {
+        style.width = Val::Percent(100. * dash.cooldown.fraction());
+    }
+}
+
+fn basket_y(play_height: f32, texture_height: f32, scale: f32) -> f32 {
+    -play_height / 2. + texture_height * scale / 2.
+}
+
+fn basket_scale(assist_settings: &AssistSettings, cheats: &Cheats) -> f32 {
+    let scale = assist_settings.basket_scale as f32 / 100.;
+    if cheats.big_basket {
+        scale * BIG_BASKET_SCALE
+    } else {
+        scale
+    }
+}
+
+// Only the width changes, and the movement clamp and catch AABB both read
+// the transform's scale so they follow along.
+fn update_widen(
+    time: Res<Time>,
+    mut power_up_events: EventReader<PowerUpCollectedEvent>,
+    assist_settings: Res<AssistSettings>,
+    cheats: Res<Cheats>,
+    mut player_query: Query<(&mut Transform, &mut Widen), With<Player>>,
+) {
+    let picked_up = power_up_events
+        .read()
+        .any(|PowerUpCollectedEvent(power_up)| *power_up == PowerUp::Widen);
+    let scale = basket_scale(&assist_settings, &cheats);
+    for (mut transform, mut widen) in &mut player_query {
+        // A second pickup refreshes the timer instead of stacking.
+        if picked_up {
+            widen.timer = Timer::from_seconds(WIDEN_DURATION, TimerMode::Once);
+        }
+        widen.timer.tick(time.delta());
+        let target = if widen.timer.finished() {
+            1.
+        } else {
+            WIDEN_SCALE
+        };
+        let step = WIDEN_EASE_SPEED * time.delta_seconds();
+        widen.width += (target - widen.width).clamp(-step, step);
+        transform.scale.x = scale * widen.width;
+    }
+}
+
+fn update_time_dilation(
+    time: Res<Time>,
+    mut power_up_events: EventReader<PowerUpCollectedEvent>,
+    mut time_dilation: ResMut<TimeDilation>,
+) {
+    for PowerUpCollectedEvent(power_up) in power_up_events.read() {
+        if *power_up == PowerUp::SlowMotion {
+            time_dilation.extend(SLOW_MOTION_DURATION);
+        }
+    }
+    time_dilation.timer.tick(time.delta());
+}
+
+fn update_multiplier(
+    time: Res<Time>,
+    mut power_up_events: EventReader<PowerUpCollectedEvent>,
+    mut multiplier: ResMut<Multiplier>,
+) {
+    for PowerUpCollectedEvent(power_up) in power_up_events.read() {
+        if *power_up == PowerUp::Star {
+            multiplier.bump();
+        }
+    }
+    // Only tick while a star is active so change detection stays quiet otherwise.
+    if multiplier.factor > 1 {
+        multiplier.tick(time.delta());
+    }
+}
+
+fn apply_basket_scale(
+    assist_settings: Res<AssistSettings>,
+    cheats: Res<Cheats>,
+    play_area: Res<PlayArea>,
+    mut player_query: Query<
+        (&mut Transform, &mut PreviousTransform, &SpriteSize, &Widen),
+        With<Player>,
+    >,
+) {
+    let scale = basket_scale(&assist_settings, &cheats);
+    for (mut transform, mut fixed, size, widen) in &mut player_query {
+        transform.scale = Vec3::new(scale * widen.width, scale, 1.);
+        transform.translation.y = basket_y(play_area.height, size.0.y, scale);
+        *fixed = PreviousTransform::new(transform.translation);
+    }
+}
+
+// Shrinking the window or leaving fullscreen narrows the play area, so the
+// basket and any apples past the new edges are pulled back in rather than
+// left out of reach.
+#[allow(clippy::type_complexity)]
+fn fit_to_play_area(
+    play_area: Res<PlayArea>,
+    mut query: Query<
+        (&mut Transform, &mut PreviousTransform, &SpriteSize),
+        Or<(With<Player>, With<Falling>)>,
+    >,
+) {
+    for (mut transform, mut fixed, size) in &mut query {
+        let range = spawn_x_range(play_area.width, size.0.x, transform.scale.x);
+        let x = transform.translation.x.clamp(-range, range);
+        if x != transform.translation.x {
+            transform.translation.x = x;
+            *fixed = PreviousTransform::new(transform.translation);
+        }
+    }
+}
+
+fn fit_canopy(play_area: Res<PlayArea>, mut query: Query<(&mut Transform, &Canopy)>) {
+    for (mut transform, canopy) in &mut query {
+        *transform = canopy_transform(&play_area, canopy.0);
+    }
+}
+
+// Scaled evenly to span the play area, hanging down from its top edge.
+fn canopy_transform(play_area: &PlayArea, texture_size: Vec2) -> Transform {
+    let scale = play_area.width / texture_size.x;
+    let y = play_area.height / 2. - texture_size.y * scale / 2.;
+    Transform::from_xyz(0., y, CANOPY_Z).with_scale(Vec3::new(scale, scale, 1.))
+}
+
+fn branch_positions(play_area: &PlayArea, texture_size: Vec2) -> Vec<Vec2> {
+    let canopy_height = texture_size.y * play_area.width / texture_size.x;
+    BRANCH_ANCHORS
+        .iter()
+        .map(|anchor| {
+            Vec2::new(
+                (anchor.x - 0.5) * play_area.width,
+                play_area.height / 2. - anchor.y * canopy_height,
+            )
+        })
+        .collect()
+}
+
+fn player_movement(
+    mut player_query: Query<
+        (&mut Transform, &SpriteSize, &mut BasketVelocity, &mut Dash),
+        With<Player>,
+    >,
+    time: Res<Time>,
+    input: Res<TickInput>,
+    input_source: Res<InputSource>,
+    movement_style: Res<MovementStyle>,
+    play_area: Res<PlayArea>,
+    config: Res<GameConfig>,
+) {
+    let (mut transform, size, mut velocity, mut dash) = match player_query.get_single_mut() {
+        Ok(player) => player,
+        Err(_) => return,
+    };
+    let texture_size = size.0 * transform.scale.truncate();
+
+    let movement = config.player_speed * time.delta_seconds();
+
+    // Only a held direction can be dashed in.
+    if input.dash && dash.cooldown.finished() && input.left != input.right {
+        dash.direction = if input.left { -1. } else { 1. };
+        dash.timer.reset();
+        dash.cooldown.reset();
+    }
+
+    if !dash.timer.finished() {
+        dash.timer.tick(time.delta());
+        transform.translation.x +=
+            dash.direction * DASH_DISTANCE / DASH_DURATION * time.delta_seconds();
+    } else if let Some(target_x) = input.target_x {
+        // A dragging finger or the cursor is followed at the basket's usual speed.
+        transform.translation.x += (target_x - transform.translation.x).clamp(-movement, movement);
+    } else if input_source.movement_style(*movement_style) == MovementStyle::Smooth {
+        let delta = time.delta_seconds();
+        let held = match (input.left, input.right) {
+            (true, false) => -1.,
+            (false, true) => 1.,
+            _ => 0.,
+        };
+        velocity.0 = if held != 0. {
+            (velocity.0 + held * config.player_acceleration * delta)
+                .clamp(-config.player_speed, config.player_speed)
+        } else {
+            velocity.0.signum() * (velocity.0.abs() - config.player_friction * delta).max(0.)
+        };
+        transform.translation.x += velocity.0 * delta;
+    } else if input.left {
+        transform.translation.x -= movement;
+    } else if input.right {
+        transform.translation.x += movement;
+    }
+    if dash.timer.finished() {
+        dash.cooldown.tick(time.delta());
+    }
+
+    let left_side = -play_area.width / 2. + texture_size.x / 2.;
+    let ride_side = play_area.width / 2. - texture_size.x / 2.;
+    // Hitting a wall kills the basket's speed, so turning back doesn't
+    // first have to undo it.
+    if transform.translation.x < left_side {
+        transform.translation.x = left_side;
+        velocity.0 = 0.;
+    } else if transform.translation.x > ride_side {
+        transform.translation.x = ride_side;
+        velocity.0 = 0.;
+    }
+}
+
+fn start_catch_flash(
+    mut commands: Commands,
+    mut caught_events: EventReader<AppleCaughtEvent>,
+    mut player_query: Query<(Entity, &mut Sprite, Option<&mut FlashTimer>), With<Player>>,
+) {
+    let points: Vec<i32> = caught_events.read().map(|event| event.points).collect();
+    if points.is_empty() {
+        return;
+    }
+    // A penalty in the same tick takes priority so it is never masked by a catch.
+    let flash_color = if points.iter().any(|&points| points < 0) {
+        PENALTY_CATCH_FLASH_COLOR
+    } else if points.iter().any(|&points| points > NORMAL_APPLE_POINTS) {
+        BONUS_CATCH_FLASH_COLOR
+    } else {
+        CATCH_FLASH_COLOR
+    };
+    for (entity, mut sprite, flash) in &mut player_query {
+        match flash {
+            Some(mut flash) => flash.timer.reset(),
+            None => {
+                commands.entity(entity).insert(FlashTimer {
+                    timer: Timer::from_seconds(CATCH_FLASH_DURATION, TimerMode::Once),
+                    original_color: sprite.color,
+                });
+            }
+        }
+        sprite.color = flash_color;
+    }
+}
+
+fn update_catch_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_query: Query<(Entity, &mut Sprite, &mut FlashTimer)>,
+) {
+    for (entity, mut sprite, mut flash) in &mut player_query {
+        flash.timer.tick(time.delta());
+        if flash.timer.finished() {
+            sprite.color = flash.original_color;
+            commands.entity(entity).remove::<FlashTimer>();
+        }
+    }
+}
+
+fn start_catch_squash(
+    mut commands: Commands,
+    mut caught_events: EventReader<AppleCaughtEvent>,
+    motion_preferences: Res<MotionPreferences>,
+    mut player_query: Query<(Entity, Option<&mut CatchSquash>), With<Player>>,
+) {
+    if caught_events.read().count() == 0 || motion_preferences.reduce_motion {
+        return;
+    }
+    for (entity, squash) in &mut player_query {
+        match squash {
+            Some(mut squash) => squash.0.reset(),
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(CatchSquash(Timer::from_seconds(
+                        CATCH_SQUASH_DURATION,
+                        TimerMode::Once,
+                    )));
+            }
+        }
+    }
+}
+
+fn update_catch_squash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut player_query: Query<(Entity, &mut Sprite, &SpriteSize, &mut CatchSquash)>,
+) {
+    for (entity, mut sprite, size, mut squash) in &mut player_query {
+        squash.0.tick(time.delta());
+        if squash.0.finished() {
+            sprite.custom_size = Some(size.0);
+            commands.entity(entity).remove::<CatchSquash>();
+            continue;
+        }
+        let remaining = squash.0.fraction_remaining();
+        let eased = remaining * remaining;
+        sprite.custom_size = Some(size.0 * Vec2::ONE.lerp(CATCH_SQUASH_SCALE, eased));
+    }
+}
+
+// Only the sprite turns; collision keeps using the upright size.
+#[allow(clippy::type_complexity)]
+fn spin_apples(
+    time: Res<Time>,
+    time_dilation: Res<TimeDilation>,
+    mut apple_query: Query<(&mut Transform, &AngularVelocity), (With<Falling>, Without<Hanging>)>,
+) {
+    let delta = time.delta_seconds() * time_dilation.scale();
+    for (mut transform, angular_velocity) in &mut apple_query {
+        transform.rotate_z(angular_velocity.0 * delta);
+    }
+}
+
+fn animate_apples(
+    time: Res<Time>,
+    time_dilation: Res<TimeDilation>,
+    mut apple_query: Query<(&mut TextureAtlas, &mut AppleAnimation)>,
+) {
+    let delta = time.delta().mul_f32(time_dilation.scale());
+    for (mut atlas, mut animation) in &mut apple_query {
+        animation.0.tick(delta);
+        if animation.0.just_finished() {
+            atlas.index =
+                (atlas.index + animation.0.times_finished_this_tick() as usize) % APPLE_FRAMES;
+        }
+    }
+}
+
+fn release_apples(
+    mut commands: Commands,
+    time: Res<Time>,
+    time_dilation: Res<TimeDilation>,
+    mut hanging_query: Query<(Entity, &mut Transform, &mut Hanging)>,
+) {
+    let delta = time.delta().mul_f32(time_dilation.scale());
+    for (entity, mut transform, mut hanging) in &mut hanging_query {
+        hanging.0.tick(delta);
+        if hanging.0.finished() {
+            transform.rotation = Quat::IDENTITY;
+            commands.entity(entity).remove::<Hanging>();
+            continue;
+        }
+        let swing = (hanging.0.elapsed_secs() * HANG_WOBBLE_RATE * TAU).sin();
+        transform.rotation = Quat::from_rotation_z(swing * HANG_WOBBLE_ANGLE);
+    }
+}
+
+// Weights are rolled when an apple appears, whichever way it was spawned.
+#[allow(clippy::type_complexity)]
+fn assign_fall_velocity(
+    mut commands: Commands,
+    new_query: Query<
+        (Entity, Option<&AppleKind>),
+        (With<Falling>, Without<Velocity>, Without<Hanging>),
+    >,
+    mutators: Res<ActiveMutators>,
+    level: Res<Level>,
+    config: Res<GameConfig>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    for (entity, kind) in &new_query {
+        let variation = game_rng
+            .rng
+            .gen_range(1. - config.fall_speed_variation..=1. + config.fall_speed_variation);
+        let weight = level.fall_speed_scale()
+            * mutators.fall_speed_scale()
+            * kind.map_or(1., |kind| kind.fall_speed_scale())
+            * variation;
+        commands.entity(entity).insert((
+            Velocity(Vec2::new(0., -config.initial_fall_speed * weight)),
+            Weight(weight),
+        ));
+    }
+}
+
+fn apply_gravity(
+    time: Res<Time>,
+    time_dilation: Res<TimeDilation>,
+    gravity: Res<Gravity>,
+    mut query: Query<(&mut Velocity, &Weight)>,
+) {
+    let delta = time.delta_seconds() * time_dilation.scale();
+    for (mut velocity, weight) in &mut query {
+        let fallen = velocity.0.y - gravity.acceleration * weight.0 * delta;
+        velocity.0.y = fallen.max(-gravity.terminal_speed * weight.0);
+    }
+}
+
+fn apply_velocity(
+    time: Res<Time>,
+    time_dilation: Res<TimeDilation>,
+    mut query: Query<(&mut Transform, &Velocity)>,
+) {
+    let delta = time.delta_seconds() * time_dilation.scale();
+    for (mut transform, velocity) in &mut query {
+        transform.translation += (velocity.0 * delta).extend(0.);
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn apple_movement(
+    mut apple_query: Query<
+        (
+            &mut Transform,
+            &SpriteSize,
+            Option<&AppleKind>,
+            Has<PowerUp>,
+            Entity,
+        ),
+        With<Falling>,
+    >,
+    player_query: Query<(&Transform, &SpriteSize), (With<Player>, Without<Falling>)>,
+    time: Res<Time>,
+    play_area: Res<PlayArea>,
+    profile: Res<Profile>,
+    input_source: Res<InputSource>,
+    mut missed_events: EventWriter<AppleMissedEvent>,
+    mut run_stats: ResMut<RunStats>,
+    mut commands: Commands,
+) {
+    let loadout = input_source.loadout(&profile);
+    let magnet = loadout.magnet_radius().and_then(|radius| {
+        let (player_transform, player_size) = player_query.get_single().ok()?;
+        Some((
+            player_transform.translation.truncate(),
+            magnet_reach(player_transform, player_size, radius),
+        ))
+    });
+    for (mut transform, size, kind, is_power_up, entity) in apple_query.iter_mut() {
+        // The magnet only pulls apples, never bombs.
+        if let Some((center, reach)) = magnet.filter(|_| kind.is_some()) {
+            let offset = center - transform.translation.truncate();
+            if offset.length() <= reach {
+                let drift = (offset.x * MAGNET_PULL).clamp(-MAGNET_MAX_SPEED, MAGNET_MAX_SPEED);
+                transform.translation.x += drift * time.delta_seconds();
+            }
+        }
+        let bottom = -play_area.height / 2. - (size.0.y * transform.scale.y) / 2.;
+
+        if transform.translation.y < bottom {
+            // Dodging a rotten apple or a bomb is the point, so neither counts
+            // as a miss.
+            if !is_power_up && kind.is_some_and(|kind| *kind != AppleKind::Rotten) {
+                missed_events.send(AppleMissedEvent {
+                    position: transform.translation,
+                });
+            } else if !is_power_up {
+                run_stats.hazards_avoided += 1;
+            }
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn score_misses(
+    mut missed_events: EventReader<AppleMissedEvent>,
+    game_mode: Res<GameMode>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut lives: ResMut<Lives>,
+    mut run_stats: ResMut<RunStats>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for _ in missed_events.read() {
+        scoreboard.combo = 0;
+        // Only the miss that takes the last life ends the run, even if more
+        // apples fall in the same tick.
+        if game_mode.has_lives() && lives.0 > 0 {
+            lives.0 -= 1;
+            let elapsed = run_stats.elapsed;
+            run_stats.life_lost_times.push(elapsed);
+            if lives.0 == 0 {
+                game_state.set(GameState::GameOver);
+            }
+        }
+    }
+}
+
+// The aura extends past the basket's edges by the upgrade radius.
+fn magnet_reach(player_transform: &Transform, player_size: &SpriteSize, radius: f32) -> f32 {
+    player_size.0.x * player_transform.scale.x / 2. + radius
+}
+
+#[allow(clippy::type_complexity)]
+fn follow_basket(
+    player_query: Query<(&Transform, &SpriteSize), (With<Player>, Without<MagnetAura>)>,
+    mut aura_query: Query<(&mut Transform, &MagnetAura)>,
+) {
+    let Ok((player_transform, player_size)) = player_query.get_single() else {
+        return;
+    };
+    for (mut transform, MagnetAura(radius)) in &mut aura_query {
+        let reach = magnet_reach(player_transform, player_size, *radius);
+        transform.translation = player_transform.translation.truncate().extend(0.5);
+        transform.scale = Vec3::new(reach, reach, 1.);
+    }
+}
+
+fn show_magnet_aura(
+    show_magnet_aura: Res<ShowMagnetAura>,
+    mut aura_query: Query<&mut Visibility, With<MagnetAura>>,
+) {
+    for mut visibility in &mut aura_query {
+        *visibility = if show_magnet_aura.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+pub fn spawn_y(play_height: f32, texture_height: f32, scale: f32) -> f32 {
+    play_height / 2. + texture_height * scale / 2. + SPAWN_MARGIN
+}
+
+pub fn spawn_x_range(play_width: f32, texture_width: f32, scale: f32) -> f32 {
+    (play_width / 2. - texture_width * scale / 2.).max(0.)
+}
+
+fn advance_difficulty(
+    time: Res<Time>,
+    config: Res<GameConfig>,
+    mut difficulty: ResMut<DifficultyCurve>,
+    mut gravity: ResMut<Gravity>,
+) {
+    difficulty.elapsed += time.delta_seconds();
+    *gravity = Gravity {
+        acceleration: difficulty.gravity(),
+        terminal_speed: config.terminal_fall_speed,
+    };
+}
+
+#[allow(clippy::too_many_arguments)]
+fn apple_spawning(
+    mut commands: Commands,
+    time: Res<Time>,
+    textures: Textures,
+    mut spawner: ResMut<AppleSpawnerConfig>,
+    difficulty: Res<DifficultyCurve>,
+    level: Res<Level>,
+    time_dilation: Res<TimeDilation>,
+    mutators: Res<ActiveMutators>,
+    mut tick_spawn: ResMut<TickSpawn>,
+    wave_player: Res<WavePlayer>,
+    play_area: Res<PlayArea>,
+    profile: Res<Profile>,
+    game_mode: Res<GameMode>,
+    mut game_rng: ResMut<GameRng>,
+    toast_query: Query<Entity, With<SpawnToast>>,
+    config: Res<GameConfig>,
+) {
+    let interval = Duration::from_secs_f32(
+        difficulty.spawn_interval()
+            * level.spawn_interval_scale()
+            * mutators.spawn_interval_scale(),
+    );
+    if spawner.timer.duration() != interval {
+        spawner.timer.set_duration(interval);
+    }
+    spawner.spawn_count = difficulty.spawn_count();
+    if !wave_player.replaces_random() {
+        spawner
+            .timer
+            .tick(time.delta().mul_f32(time_dilation.scale()));
+    }
+    let request = tick_spawn.0.take();
+    if !spawner.timer.finished() && request.is_none() {
+        return;
+    }
+
+    let texture_size = match textures.sprite(Texture::Apple) {
+        Some((_, size)) => size,
+        None => return,
+    };
+    let top = spawn_y(play_area.height, texture_size.y, config.apple_scale);
+    let spawn_range = spawn_x_range(play_area.width, texture_size.x, config.apple_scale);
+
+    if spawner.timer.finished() {
+        let apple_width = texture_size.x * config.apple_scale;
+        // Without the tree, apples appear at random above the screen.
+        let branches = textures
+            .sprite(Texture::Canopy)
+            .filter(|_| config.apple_tree)
+            .map(|(_, canopy_size)| branch_positions(&play_area, canopy_size));
+        let spawns: Vec<Vec3> = match &branches {
+            Some(branches) => spawner
+                .pick_branches(&mut game_rng.rng, branches, apple_width)
+                .into_iter()
+                .map(|branch| branch.extend(0.))
+                .collect(),
+            None => spawner
+                .pick_spawn_xs(&mut game_rng.rng, spawn_range, apple_width)
+                .into_iter()
+                .map(|x| Vec3::new(x, top, 0.))
+                .collect(),
+        };
+        // One apple of a group is never a bomb, so there is always something to catch.
+        let safe = (spawns.len() > 1).then(|| game_rng.rng.gen_range(0..spawns.len()));
+        for (index, translation) in spawns.into_iter().enumerate() {
+            let power_up =
+                if *game_mode == GameMode::Endless && game_rng.rng.gen_bool(REWIND_PICKUP_CHANCE) {
+                    Some(PowerUp::Rewind)
+                } else if game_rng.rng.gen_bool(WIDEN_PICKUP_CHANCE) {
+                    Some(PowerUp::Widen)
+                } else if game_rng.rng.gen_bool(SLOW_MOTION_PICKUP_CHANCE) {
+                    Some(PowerUp::SlowMotion)
+                } else if game_rng.rng.gen_bool(STAR_PICKUP_CHANCE) {
+                    Some(PowerUp::Star)
+                } else {
+                    None
+                };
+            // Bombs are left out of modes without lives to lose.
+            let spawned = if power_up.is_none()
+                && game_mode.has_lives()
+                && Some(index) != safe
+                && game_rng.rng.gen_bool(BOMB_CHANCE)
+            {
+                spawn_bomb(&mut commands, &textures, translation)
+            } else {
+                let golden = spawner.next_is_golden();
+                let kind = match power_up {
+                    Some(_) => AppleKind::Normal,
+                    None if golden => AppleKind::Golden,
+                    None => AppleKind::roll(&mut game_rng.rng),
+                };
+                spawn_apple(
+                    &mut commands,
+                    &textures,
+                    &profile,
+                    &config,
+                    &mut game_rng.rng,
+                    translation,
+                    kind,
+                    power_up,
+                )
+            };
+            if let Some(entity) = spawned.filter(|_| branches.is_some()) {
+                commands
+                    .entity(entity)
+                    .insert(Hanging(Timer::from_seconds(HANG_DURATION, TimerMode::Once)));
+            }
+        }
+    }
+    if let Some(request) = request {
+        let spawn_x = spawner.pick_spawn_x(
+            &mut game_rng.rng,
+            spawn_range,
+            texture_size.x * config.apple_scale,
+        );
+        let translation = Vec3::new(spawn_x, top, 0.);
+        // Modes without lives have no bombs, so chat drops an apple instead.
+        let drop = match request.drop {
+            RequestedDrop::Bomb if !game_mode.has_lives() => RequestedDrop::Apple,
+            drop => drop,
+        };
+        let kind = match drop {
+            RequestedDrop::Bomb => None,
+            RequestedDrop::Golden => Some(AppleKind::Golden),
+            RequestedDrop::Apple => Some(AppleKind::Normal),
+        };
+        match kind {
+            Some(kind) => spawn_apple(
+                &mut commands,
+                &textures,
+                &profile,
+                &config,
+                &mut game_rng.rng,
+                translation,
+                kind,
+                None,
+            ),
+            None => spawn_bomb(&mut commands, &textures, translation),
+        };
+
+        for entity in &toast_query {
+            commands.entity(entity).despawn_recursive();
+        }
+        commands.spawn((
+            TextBundle::from_section(
+                format!("{} dropped {}", request.viewer, drop.label()),
+                TextStyle {
+                    font_size: 22.,
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(40.),
+                right: Val::Px(10.),
+                ..default()
+            }),
+            SpawnToast(Timer::from_seconds(SPAWN_TOAST_DURATION, TimerMode::Once)),
+            OnGameScreen,
+        ));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_apple(
+    commands: &mut Commands,
+    textures: &Textures,
+    profile: &Profile,
+    config: &GameConfig,
+    rng: &mut impl Rng,
+    translation: Vec3,
+    kind: AppleKind,
+    power_up: Option<PowerUp>,
+) -> Option<Entity> {
+    let mut atlas = None;
+    let variant = match kind {
+        AppleKind::Golden => textures.sprite(Texture::GoldenApple),
+        AppleKind::Rotten => textures.sprite(Texture::RottenApple),
+        AppleKind::Normal => None,
+    };
+    let (texture, texture_size, color, scale) = match variant {
+        Some((texture, size)) => (texture, size, Color::WHITE, config.apple_scale),
+        None => {
+            let (texture, size) = textures.sprite(Texture::Apple)?;
+            atlas = textures.atlas(Texture::Apple);
+            let (color, scale) = match (power_up, kind) {
+                (Some(power_up), _) => (power_up.tint(), config.apple_scale),
+                (None, AppleKind::Golden) => (
+                    GOLDEN_FALLBACK_TINT,
+                    config.apple_scale * GOLDEN_FALLBACK_SCALE,
+                ),
+                (None, AppleKind::Rotten) => (ROTTEN_FALLBACK_TINT, config.apple_scale),
+                (None, AppleKind::Normal) => {
+                    (profile.active_apple_theme().tint(), config.apple_scale)
+                }
+            };
+            (texture, size, color, scale)
+        }
+    };
+    let mut apple = commands.spawn((
+        SpriteBundle {
+            transform: Transform {
+                translation,
+                scale: Vec3::splat(scale),
+                ..default()
+            },
+            texture,
+            sprite: Sprite {
+                color,
+                custom_size: Some(texture_size),
+                ..default()
+            },
+            ..default()
+        },
+        Apple,
+        Falling,
+        kind,
+        PreviousTransform::new(translation),
+        OnGameScreen,
+        SpriteSize(texture_size),
+        AngularVelocity(rng.gen_range(-MAX_APPLE_SPIN..=MAX_APPLE_SPIN)),
+    ));
+    if let Some(power_up) = power_up {
+        apple.insert(power_up);
+    }
+    if let Some(mut atlas) = atlas {
+        // Offset each apple into the loop so they don't all shine in unison.
+        atlas.index = rng.gen_range(0..APPLE_FRAMES);
+        apple.insert((
+            atlas,
+            AppleAnimation(Timer::from_seconds(
+                APPLE_FRAME_DURATION,
+                TimerMode::Repeating,
+            )),
+        ));
+    }
+    Some(apple.id())
+}
+
+pub fn spawn_bomb(
+    commands: &mut Commands,
+    textures: &Textures,
+    translation: Vec3,
+) -> Option<Entity> {
+    let (texture, texture_size) = textures.sprite(Texture::Bomb)?;
+    let bomb = commands.spawn((
+        SpriteBundle {
+            transform: Transform {
+                translation,
+                scale: Vec3::splat(BOMB_SCALE),
+                ..default()
+            },
+            texture,
+            sprite: Sprite {
+                custom_size: Some(texture_size),
+                ..default()
+            },
+            ..default()
+        },
+        Bomb,
+        Falling,
+        PreviousTransform::new(translation),
+        OnGameScreen,
+        SpriteSize(texture_size),
+    ));
+    Some(bomb.id())
+}
+
+fn update_points_text(
+    scoreboard: Res<Scoreboard>,
+    high_score: Res<HighScore>,
+    mut points_text_query: Query<&mut Text, (With<PointsText>, Without<HighScoreText>)>,
+    mut high_score_text_query: Query<&mut Text, With<HighScoreText>>,
+) {
+    if !scoreboard.is_changed() {
+        return;
+    }
+    for mut points_text in &mut points_text_query {
+        points_text.sections[1].value = scoreboard.score.to_string();
+    }
+    let best = high_score.score.max(scoreboard.score.max(0) as u32);
+    for mut high_score_text in &mut high_score_text_query {
+        high_score_text.sections[0].value = format!("Best: {best}");
+    }
+}
+
+fn update_multiplier_text(
+    multiplier: Res<Multiplier>,
+    mut points_text_query: Query<&mut Text, With<PointsText>>,
+) {
+    if !multiplier.is_changed() {
+        return;
+    }
+    for mut points_text in &mut points_text_query {
+        points_text.sections[2].value = match multiplier.factor {
+            1 => String::new(),
+            factor => format!(" x{factor}"),
+        };
+    }
+}
+
+// Every bonus milestone makes the counter pop, or just flash with reduced motion.
+fn update_combo_text(
+    time: Res<Time>,
+    scoreboard: Res<Scoreboard>,
+    motion_preferences: Res<MotionPreferences>,
+    mut combo_text_query: Query<(&mut Text, &mut Transform, &mut ComboText)>,
+) {
+    for (mut text, mut transform, mut combo_text) in &mut combo_text_query {
+        if combo_text.shown != scoreboard.combo {
+            combo_text.shown = scoreboard.combo;
+            text.sections[0].value = format!("Combo: {}", scoreboard.combo);
+            if scoreboard.combo > 0 && scoreboard.combo.is_multiple_of(COMBO_BONUS_INTERVAL) {
+                combo_text.pulse.reset();
+            }
+        }
+        combo_text.pulse.tick(time.delta());
+        let fade = combo_text.pulse.fraction_remaining();
+        text.sections[0].style.color = Color::WHITE.mix(&COMBO_PULSE_COLOR, fade);
+        transform.scale = if motion_preferences.reduce_motion {
+            Vec3::ONE
+        } else {
+            Vec3::splat(1. + 0.4 * fade)
+        };
+    }
+}
+
+// Only the latest level gets a banner, even if several were crossed at once.
+fn show_level_banner(
+    mut commands: Commands,
+    level: Res<Level>,
+    banner_query: Query<Entity, With<LevelBanner>>,
+) {
+    if !level.is_changed() || level.0 <= 1 {
+        return;
+    }
+    for entity in &banner_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            LevelBanner(Timer::from_seconds(LEVEL_BANNER_DURATION, TimerMode::Once)),
+            OnGameScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("Level {}!", level.0),
+                TextStyle {
+                    font_size: 60.,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn fade_level_banner(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut banner_query: Query<(Entity, &mut LevelBanner, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (entity, mut banner, children) in &mut banner_query {
+        banner.0.tick(time.delta());
+        if banner.0.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let alpha = banner.0.fraction_remaining();
+        for &child in children {
+            if let Ok(mut text) = text_query.get_mut(child) {
+                for section in &mut text.sections {
+                    section.style.color.set_alpha(alpha);
+                }
+            }
+        }
+    }
+}
+
+fn reset_combo(scoreboard: Option<ResMut<Scoreboard>>) {
+    if let Some(mut scoreboard) = scoreboard {
+        scoreboard.combo = 0;
+    }
+}
+
+fn update_spawn_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut toast_query: Query<(Entity, &mut SpawnToast)>,
+) {
+    for (entity, mut toast) in &mut toast_query {
+        toast.0.tick(time.delta());
+        if toast.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn apple_catching(
+    mut commands: Commands,
+    mut apple_query: Query<
+        (
+            &mut Transform,
+            &SpriteSize,
+            &mut PreviousTransform,
+            Option<&AppleKind>,
+            Option<&PowerUp>,
+            Has<Bomb>,
+            Entity,
+        ),
+        (With<Falling>, Without<Player>),
+    >,
+    player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &PreviousTransform,
+            &SpriteSize,
+            Option<&Hitbox>,
+            Has<LitBomb>,
+        ),
+        With<Player>,
+    >,
+    mut caught_events: EventWriter<AppleCaughtEvent>,
+    mut power_up_events: EventWriter<PowerUpCollectedEvent>,
+    mut lives: ResMut<Lives>,
+    mut run_stats: ResMut<RunStats>,
+    mut game_state: ResMut<NextState<GameState>>,
+    multiplier: Res<Multiplier>,
+) {
+    let Ok((player, player_transform, player_fixed, player_size, hitbox, mut bomb_lit)) =
+        player_query.get_single()
+    else {
+        return;
+    };
+
+    let player_aabb = swept_aabb(player_transform, player_fixed, player_size, hitbox);
+    let basket = collision_aabb(player_transform, player_size, hitbox);
+
+    for (mut transform, size, mut fixed, kind, power_up, is_bomb, entity) in apple_query.iter_mut()
+    {
+        let half_size = (size.0 * transform.scale.truncate()) / 2.;
+        let box_aabb = Aabb2d::new(transform.translation.truncate(), half_size);
+        let caught = entered_from_above(
+            &player_aabb,
+            fixed.previous().truncate(),
+            transform.translation.truncate(),
+            half_size.y,
+        );
+        // A bomb landing in the basket is lit and can still be thrown out,
+        // unless one is burning there already. Any other contact sets it off.
+        if is_bomb {
+            if caught && !bomb_lit {
+                commands.entity(entity).despawn();
+                commands.entity(player).insert(LitBomb::default());
+                bomb_lit = true;
+                continue;
+            }
+            if caught || player_aabb.intersects(&box_aabb) {
+                commands.entity(entity).despawn();
+                let elapsed = run_stats.elapsed;
+                run_stats.life_lost_times.push(elapsed);
+                lives.0 = 0;
+                game_state.set(GameState::GameOver);
+                return;
+            }
+            continue;
+        }
+        let Some(kind) = kind else {
+            continue;
+        };
+        if caught {
+            if let Some(&power_up) = power_up {
+                power_up_events.send(PowerUpCollectedEvent(power_up));
+                commands.entity(entity).despawn();
+                continue;
+            }
+            // Stars only multiply rewards, never the rotten apple penalty.
+            let points = match kind.points() {
+                points if points > 0 => points * multiplier.factor as i32,
+                points => points,
+            };
+            caught_events.send(AppleCaughtEvent {
+                position: transform.translation,
+                points,
+                perfect: (transform.translation.x - basket.center().x).abs()
+                    <= basket.half_size().x * PERFECT_CATCH_FRACTION,
+            });
+            commands.get_entity(entity).unwrap().despawn();
+        } else if player_aabb.intersects(&box_aabb) {
+            transform.translation.x = deflect_x(&player_aabb, transform.translation.x, half_size.x);
+            // Snapped, or the apple is drawn sliding out through the basket.
+            *fixed = PreviousTransform::new(transform.translation);
+        }
+    }
+}
+
+fn score_catches(
+    mut caught_events: EventReader<AppleCaughtEvent>,
+    mut combo_events: EventWriter<ComboMilestoneEvent>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut run_stats: ResMut<RunStats>,
+    mut level: ResMut<Level>,
+    profile: Res<Profile>,
+    input_source: Res<InputSource>,
+) {
+    let loadout = input_source.loadout(&profile);
+    for event in caught_events.read() {
+        if event.points < 0 {
+            scoreboard.score = (scoreboard.score + event.points).max(0);
+            scoreboard.combo = 0;
+            continue;
+        }
+        let points =
+            event.points as f32 * loadout.score_multiplier() + scoreboard.fractional_points;
+        scoreboard.score += points.trunc() as i32;
+        scoreboard.fractional_points = points.fract();
+        scoreboard.combo += 1;
+        if scoreboard.combo.is_multiple_of(COMBO_BONUS_INTERVAL) {
+            scoreboard.score += (scoreboard.combo / COMBO_BONUS_INTERVAL) as i32;
+        }
+        run_stats.best_combo = run_stats.best_combo.max(scoreboard.combo);
+        if COMBO_MILESTONES.contains(&scoreboard.combo) {
+            combo_events.send(ComboMilestoneEvent {
+                combo: scoreboard.combo,
+            });
+        }
+    }
+    // Losing points to a rotten apple never drops a level.
+    let reached = Level::for_score(scoreboard.score);
+    if reached > level.0 {
+        level.0 = reached;
+    }
+}
+
+fn entered_from_above(basket: &Aabb2d, previous: Vec2, current: Vec2, half_height: f32) -> bool {
+    let top = basket.max.y;
+    let previous_bottom = previous.y - half_height;
+    let current_bottom = current.y - half_height;
+    if previous_bottom < top || current_bottom > top {
+        return false;
+    }
+
+    let travelled = previous_bottom - current_bottom;
+    let t = if travelled > 0. {
+        (previous_bottom - top) / travelled
+    } else {
+        0.
+    };
+    let crossing_x = previous.x.lerp(current.x, t);
+    (basket.min.x..=basket.max.x).contains(&crossing_x)
+}
+
+fn deflect_x(basket: &Aabb2d, x: f32, half_width: f32) -> f32 {
+    if x < basket.center().x {
+        basket.min.x - half_width
+    } else {
+        basket.max.x + half_width
+    }
+}
+
+fn collision_aabb(transform: &Transform, size: &SpriteSize, hitbox: Option<&Hitbox>) -> Aabb2d {
+    let scale = transform.scale.truncate();
+    match hitbox {
+        Some(Hitbox(half_extents, offset)) => Aabb2d::new(
+            transform.translation.truncate() + *offset * scale,
+            *half_extents * scale,
+        ),
+        None => Aabb2d::new(transform.translation.truncate(), (size.0 * scale) / 2.),
+    }
+}
+
+// Swept across the basket's movement this tick, so a dash can't skip over an
+// apple it passes under.
+fn swept_aabb(
+    transform: &Transform,
+    fixed: &PreviousTransform,
+    size: &SpriteSize,
+    hitbox: Option<&Hitbox>,
+) -> Aabb2d {
+    let start = Transform {
+        translation: fixed.previous(),
+        ..*transform
+    };
+    collision_aabb(transform, size, hitbox).merge(&collision_aabb(&start, size, hitbox))
+}
+
+// F3 belongs to the debug overlay, so hitboxes have a key of their own.
+fn toggle_hitboxes(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut show_hitboxes: ResMut<ShowHitboxes>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F4) {
+        show_hitboxes.0 = !show_hitboxes.0;
+    }
+}
+
+fn draw_hitboxes(
+    mut gizmos: Gizmos,
+    player_query: Query<(&Transform, &SpriteSize, Option<&Hitbox>), With<Player>>,
+) {
+    for (transform, size, hitbox) in player_query.iter() {
+        let aabb = collision_aabb(transform, size, hitbox);
+        gizmos.rect_2d(
+            aabb.center(),
+            0.,
+            aabb.half_size() * 2.,
+            Color::srgb(0., 1., 0.),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCALES: [f32; 3] = [1., 1.5, 2.];
+    const PLAY_AREA: Vec2 = Vec2::new(1280., 720.);
+    const APPLE_SIZE: Vec2 = Vec2::new(128., 128.);
+    const BASKET_SIZE: Vec2 = Vec2::new(128., 69.);
+    const APPLE_HALF_HEIGHT: f32 = 32.;
+
+    #[test]
+    fn apples_spawn_fully_above_the_play_area() {
+        for scale in SCALES {
+            let y = spawn_y(PLAY_AREA.y, APPLE_SIZE.y, scale);
+            let bottom = y - APPLE_SIZE.y * scale / 2.;
+            assert!(bottom > PLAY_AREA.y / 2., "scale {scale}");
+        }
+    }
+
+    #[test]
+    fn apples_spawn_inside_the_side_edges() {
+        for scale in SCALES {
+            let range = spawn_x_range(PLAY_AREA.x, APPLE_SIZE.x, scale);
+            let right_edge = range + APPLE_SIZE.x * scale / 2.;
+            assert!(range > 0., "scale {scale}");
+            assert_eq!(right_edge, PLAY_AREA.x / 2., "scale {scale}");
+        }
+    }
+
+    #[test]
+    fn the_basket_rests_on_the_bottom_edge() {
+        for scale in SCALES {
+            let y = basket_y(PLAY_AREA.y, BASKET_SIZE.y, scale);
+            let bottom = y - BASKET_SIZE.y * scale / 2.;
+            assert_eq!(bottom, -PLAY_AREA.y / 2., "scale {scale}");
+        }
+    }
+
+    #[test]
+    fn a_fast_apple_crossing_the_whole_basket_in_one_tick_is_caught() {
+        let basket = Aabb2d::new(Vec2::ZERO, BASKET_SIZE / 2.);
+        let previous = Vec2::new(10., 200.);
+        let current = Vec2::new(10., -200.);
+
+        assert!(entered_from_above(
+            &basket,
+            previous,
+            current,
+            APPLE_HALF_HEIGHT
+        ));
+    }
+
+    #[test]
+    fn an_apple_beside_the_basket_is_not_caught() {
+        let basket = Aabb2d::new(Vec2::ZERO, BASKET_SIZE / 2.);
+        let previous = Vec2::new(200., 200.);
+        let current = Vec2::new(200., -200.);
+
+        assert!(!entered_from_above(
+            &basket,
+            previous,
+            current,
+            APPLE_HALF_HEIGHT
+        ));
+    }
+
+    #[test]
+    fn a_dash_catches_an_apple_it_passes_under() {
+        // Where the basket started the tick it dashed 400 units in.
+        let fixed = PreviousTransform::new(Vec3::new(-200., 0., 1.));
+        let transform = Transform::from_translation(Vec3::new(200., 0., 1.));
+        let basket = swept_aabb(&transform, &fixed, &SpriteSize(BASKET_SIZE), None);
+        let previous = Vec2::new(0., 80.);
+        let current = Vec2::new(0., 20.);
+
+        assert!(entered_from_above(
+            &basket,
+            previous,
+            current,
+            APPLE_HALF_HEIGHT
+        ));
+        let end_only = collision_aabb(&transform, &SpriteSize(BASKET_SIZE), None);
+        assert!(!entered_from_above(
+            &end_only,
+            previous,
+            current,
+            APPLE_HALF_HEIGHT
+        ));
+    }
+
+    #[test]
+    fn spawn_x_keeps_apples_on_screen_at_any_width() {
+        for width in [480., 800., 1280., 1920., 3840.] {
+            for scale in [0.5, 1., 1.5, 2.] {
+                let range = spawn_x_range(width, APPLE_SIZE.x, scale);
+                let half_width = APPLE_SIZE.x * scale / 2.;
+                assert!(range >= 0., "width {width} scale {scale}");
+                assert!(
+                    range + half_width <= width / 2.,
+                    "width {width} scale {scale}"
+                );
+                assert!(
+                    -range - half_width >= -width / 2.,
+                    "width {width} scale {scale}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn spawn_x_centres_apples_wider_than_the_play_area() {
+        assert_eq!(spawn_x_range(100., APPLE_SIZE.x, 2.), 0.);
+    }
+}
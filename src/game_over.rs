@@ -0,0 +1,220 @@
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen,
+    persistence::{store_replay, DataDir},
+    replay::Replay,
+    results::breakdown,
+    start_run, Difficulty, GameConfig, GameMode, GameState, RunStats, Scoreboard, HOVERED_BUTTON,
+    NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+#[derive(Component)]
+struct OnGameOverScreen;
+
+#[derive(Component)]
+pub struct SaveReplayText;
+
+#[derive(Component)]
+enum MenuButtonAction {
+    Retry,
+    SaveReplay,
+    MainMenu,
+}
+
+pub fn game_over_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::GameOver), setup)
+        .add_systems(
+            Update,
+            (button_system, menu_action).run_if(in_state(GameState::GameOver)),
+        )
+        .add_systems(
+            OnExit(GameState::GameOver),
+            despawn_screen::<OnGameOverScreen>,
+        );
+}
+
+fn setup(mut commands: Commands, scoreboard: Res<Scoreboard>, run_stats: Res<RunStats>) {
+    let button_style = Style {
+        width: Val::Px(250.0),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font_size: 40.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnGameOverScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Game Over",
+                        TextStyle {
+                            font_size: 80.,
+                            ..default()
+                        },
+                    ));
+                    parent.spawn(TextBundle::from_section(
+                        format!("Final score: {}", scoreboard.score),
+                        TextStyle {
+                            font_size: 40.,
+                            ..default()
+                        },
+                    ));
+                    for line in [
+                        format!("Best combo: {}", run_stats.best_combo),
+                        breakdown(&run_stats),
+                    ] {
+                        parent.spawn(TextBundle::from_section(
+                            line,
+                            TextStyle {
+                                font_size: 30.,
+                                ..default()
+                            },
+                        ));
+                    }
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Retry,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Retry",
+                                button_text_style.clone(),
+                            ));
+                        });
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::SaveReplay,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section("Save Replay", button_text_style.clone()),
+                                SaveReplayText,
+                            ));
+                        });
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::MainMenu,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Main Menu",
+                                button_text_style.clone(),
+                            ));
+                        });
+                });
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut commands: Commands,
+    mut game_state: ResMut<NextState<GameState>>,
+    game_mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    config: Res<GameConfig>,
+    recording: Res<Replay>,
+    dir: Res<DataDir>,
+    mut text_query: Query<&mut Text, With<SaveReplayText>>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Retry => {
+                    start_run(&mut commands, &config, *game_mode, *difficulty);
+                    game_state.set(GameState::Game);
+                }
+                MenuButtonAction::SaveReplay => {
+                    save_replay(&dir, &recording, &mut text_query);
+                }
+                MenuButtonAction::MainMenu => {
+                    game_state.set(GameState::MainMenu);
+                }
+            }
+        }
+    }
+}
+
+// Shared with the pause menu, which saves the run recorded so far.
+pub fn save_replay(
+    dir: &DataDir,
+    recording: &Replay,
+    text_query: &mut Query<&mut Text, With<SaveReplayText>>,
+) {
+    let label = match store_replay(dir, recording) {
+        Ok(()) => "Replay Saved",
+        Err(err) => {
+            warn!("Failed to save the replay: {err}");
+            "Save Failed"
+        }
+    };
+    for mut text in text_query {
+        text.sections[0].value = label.to_string();
+    }
+}
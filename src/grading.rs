@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assists::run_is_unassisted, cheats::run_is_clean, persistence::Profile, GameMode, GameState,
+    RunStats,
+};
+
+// Minimum points for each grade, best first. Anything lower is a D.
+const GRADE_THRESHOLDS: [(Grade, f32); 4] = [
+    (Grade::S, 105.),
+    (Grade::A, 90.),
+    (Grade::B, 75.),
+    (Grade::C, 55.),
+];
+const COMBO_POINTS: f32 = 0.5;
+const MAX_COMBO_POINTS: f32 = 15.;
+const HAZARD_POINTS: f32 = 1.;
+const MAX_HAZARD_POINTS: f32 = 10.;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum Grade {
+    D,
+    C,
+    B,
+    A,
+    S,
+}
+
+impl Grade {
+    pub fn letter(self) -> &'static str {
+        match self {
+            Grade::S => "S",
+            Grade::A => "A",
+            Grade::B => "B",
+            Grade::C => "C",
+            Grade::D => "D",
+        }
+    }
+}
+
+pub struct RunGrade {
+    pub accuracy: f32,
+    pub best_combo: u32,
+    pub combo_points: f32,
+    pub hazards_avoided: u32,
+    pub hazard_points: f32,
+    pub grade: Grade,
+}
+
+impl RunGrade {
+    pub fn summary(&self) -> String {
+        format!(
+            "Accuracy {:.0}% + combo {} (+{:.0}) + dodged {} (+{:.0}) -> {}",
+            self.accuracy * 100.,
+            self.best_combo,
+            self.combo_points,
+            self.hazards_avoided,
+            self.hazard_points,
+            self.grade.letter()
+        )
+    }
+}
+
+// Points are accuracy as a percentage plus capped bonuses for the best combo
+// and for each hazard let fall.
+pub fn grade_run(run_stats: &RunStats) -> Option<RunGrade> {
+    let catches = run_stats.catch_xs.len();
+    let attempts = catches + run_stats.miss_xs.len();
+    if attempts == 0 {
+        return None;
+    }
+
+    let accuracy = catches as f32 / attempts as f32;
+    let combo_points = (run_stats.best_combo as f32 * COMBO_POINTS).min(MAX_COMBO_POINTS);
+    let hazard_points = (run_stats.hazards_avoided as f32 * HAZARD_POINTS).min(MAX_HAZARD_POINTS);
+    let points = accuracy * 100. + combo_points + hazard_points;
+    let grade = GRADE_THRESHOLDS
+        .iter()
+        .find(|(_, threshold)| points >= *threshold)
+        .map_or(Grade::D, |&(grade, _)| grade);
+
+    Some(RunGrade {
+        accuracy,
+        best_combo: run_stats.best_combo,
+        combo_points,
+        hazards_avoided: run_stats.hazards_avoided,
+        hazard_points,
+        grade,
+    })
+}
+
+pub fn grading_plugin(app: &mut App) {
+    app.add_systems(
+        OnEnter(GameState::Results),
+        record_best_grade.run_if(run_is_clean.and_then(run_is_unassisted)),
+    );
+}
+
+fn record_best_grade(
+    run_stats: Res<RunStats>,
+    game_mode: Res<GameMode>,
+    mut profile: ResMut<Profile>,
+) {
+    let Some(run_grade) = grade_run(&run_stats) else {
+        return;
+    };
+    let best = profile.best_grades.get(&game_mode).copied();
+    if best.is_none_or(|best| run_grade.grade > best) {
+        profile.best_grades.insert(*game_mode, run_grade.grade);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(catches: usize, misses: usize, best_combo: u32, hazards_avoided: u32) -> RunStats {
+        RunStats {
+            catch_xs: vec![0.; catches],
+            miss_xs: vec![0.; misses],
+            best_combo,
+            hazards_avoided,
+            ..default()
+        }
+    }
+
+    fn grade(run_stats: RunStats) -> Grade {
+        grade_run(&run_stats).unwrap().grade
+    }
+
+    #[test]
+    fn no_attempts_has_no_grade() {
+        assert!(grade_run(&run(0, 0, 0, 0)).is_none());
+        assert!(grade_run(&run(0, 0, 0, 5)).is_none());
+    }
+
+    #[test]
+    fn each_threshold_is_inclusive() {
+        assert_eq!(grade(run(4, 0, 10, 0)), Grade::S);
+        assert_eq!(grade(run(4, 0, 9, 0)), Grade::A);
+        assert_eq!(grade(run(3, 1, 30, 0)), Grade::A);
+        assert_eq!(grade(run(3, 1, 29, 0)), Grade::B);
+        assert_eq!(grade(run(3, 1, 0, 0)), Grade::B);
+        assert_eq!(grade(run(1, 1, 10, 0)), Grade::C);
+        assert_eq!(grade(run(1, 1, 9, 0)), Grade::D);
+    }
+
+    #[test]
+    fn dodged_hazards_count_towards_the_grade() {
+        assert_eq!(grade(run(1, 1, 0, 5)), Grade::C);
+        assert_eq!(grade(run(1, 1, 0, 4)), Grade::D);
+    }
+
+    #[test]
+    fn bonuses_are_capped() {
+        assert_eq!(grade(run(1, 1, 1000, 1000)), Grade::B);
+    }
+}
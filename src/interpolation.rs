@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct PreviousTransform {
+    previous: Vec3,
+    current: Vec3,
+}
+
+impl PreviousTransform {
+    pub fn new(translation: Vec3) -> Self {
+        Self {
+            previous: translation,
+            current: translation,
+        }
+    }
+
+    pub fn previous(&self) -> Vec3 {
+        self.previous
+    }
+}
+
+pub fn interpolation_plugin(app: &mut App) {
+    app.add_systems(FixedFirst, restore_fixed_translation)
+        .add_systems(FixedLast, record_fixed_translation)
+        .add_systems(Update, interpolate_translation);
+}
+
+fn restore_fixed_translation(mut query: Query<(&mut Transform, &mut PreviousTransform)>) {
+    for (mut transform, mut fixed) in &mut query {
+        transform.translation = fixed.current;
+        fixed.previous = fixed.current;
+    }
+}
+
+fn record_fixed_translation(mut query: Query<(&Transform, &mut PreviousTransform)>) {
+    for (transform, mut fixed) in &mut query {
+        fixed.current = transform.translation;
+    }
+}
+
+fn interpolate_translation(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &PreviousTransform)>,
+) {
+    let overstep = fixed_time.overstep_fraction();
+    for (mut transform, fixed) in &mut query {
+        transform.translation = fixed.previous.lerp(fixed.current, overstep);
+    }
+}
@@ -0,0 +1,1104 @@
+use std::{collections::VecDeque, ops::RangeInclusive, time::Duration};
+
+use bevy::{
+    prelude::*,
+    window::{
+        PresentMode, PrimaryWindow, WindowResizeConstraints, WindowResized, WindowResolution,
+    },
+};
+use bevy_asset_loader::{
+    asset_collection::AssetCollection,
+    loading_state::{config::ConfigureLoadingState, LoadingState, LoadingStateAppExt},
+};
+use config::GameConfig;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use textures::{Background, Texture, Textures};
+
+#[derive(AssetCollection, Resource)]
+pub struct ImageAssets {
+    #[asset(path = "textures/basket.png")]
+    pub player: Handle<Image>,
+    #[asset(texture_atlas_layout(tile_size_x = 128, tile_size_y = 128, columns = 4, rows = 1))]
+    pub apple_layout: Handle<TextureAtlasLayout>,
+    #[asset(path = "textures/apple_sheet.png")]
+    pub apple: Handle<Image>,
+    #[asset(path = "textures/golden_apple.png")]
+    pub golden_apple: Handle<Image>,
+    #[asset(path = "textures/rotten_apple.png")]
+    pub rotten_apple: Handle<Image>,
+    #[asset(path = "textures/bomb.png")]
+    pub bomb: Handle<Image>,
+    #[asset(path = "textures/background.png")]
+    pub background: Handle<Image>,
+    #[asset(path = "textures/canopy.png")]
+    pub canopy: Handle<Image>,
+    #[asset(path = "textures/bird.png")]
+    pub bird: Handle<Image>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct FontAssets {
+    #[asset(path = "fonts/DejaVuSans-Bold.ttf")]
+    pub bold: Handle<Font>,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
+pub enum GameState {
+    #[default]
+    Loading,
+    Loaded,
+    MainMenu,
+    Game,
+    // Passed through for a single frame so restarting exits and re-enters `Game`.
+    Restarting,
+    Results,
+    GameOver,
+    Stats,
+    Customize,
+    LoadError,
+}
+
+// Only exists during a run, so every run starts from the default and nothing
+// about a previous run's pause can leak into the next one.
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, SubStates)]
+#[source(GameState = GameState::Game)]
+pub enum PauseMode {
+    #[default]
+    Countdown,
+    Playing,
+    Paused,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug, Hash, Default, States)]
+pub enum SettingsMenu {
+    #[default]
+    Closed,
+    Open,
+    Controls,
+    Audio,
+}
+
+#[derive(Resource)]
+struct MotionPreferences {
+    reduce_motion: bool,
+}
+
+impl Default for MotionPreferences {
+    fn default() -> Self {
+        Self {
+            reduce_motion: os_prefers_reduced_motion(),
+        }
+    }
+}
+
+#[derive(Resource, Clone, Serialize, Deserialize)]
+struct AssistSettings {
+    game_speed: u32,
+    basket_scale: u32,
+}
+
+impl Default for AssistSettings {
+    fn default() -> Self {
+        Self {
+            game_speed: 100,
+            basket_scale: 100,
+        }
+    }
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default)]
+enum TextureQuality {
+    #[default]
+    Auto,
+    Standard,
+    Hd,
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default)]
+enum ControlScheme {
+    #[default]
+    Keyboard,
+    Mouse,
+}
+
+// How the basket answers the movement keys: easing up to speed and sliding to a
+// stop, or moving at full speed only while a key is held.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum MovementStyle {
+    #[default]
+    Smooth,
+    Instant,
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default)]
+enum TouchControls {
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+#[derive(Resource, Default, Clone)]
+pub struct ActionState {
+    pub left: bool,
+    pub right: bool,
+    pub pause: bool,
+    pub rewind: bool,
+    pub dash: bool,
+    pub mute: bool,
+    pub defuse: bool,
+    pub menu_up: bool,
+    pub menu_down: bool,
+    pub confirm: bool,
+    // World x of a finger dragging on the screen.
+    pub drag_x: Option<f32>,
+}
+
+#[derive(Resource)]
+struct ShowMagnetAura(bool);
+
+impl Default for ShowMagnetAura {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    vsync: bool,
+    fullscreen: bool,
+    // `None` is the primary monitor, otherwise an index into the available monitors.
+    monitor: Option<usize>,
+}
+
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioSettings {
+    master: f32,
+    music: f32,
+    sfx: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 0.7,
+            music: 0.7,
+            sfx: 0.7,
+        }
+    }
+}
+
+impl AudioSettings {
+    fn music_volume(&self) -> f32 {
+        self.master * self.music
+    }
+
+    fn sfx_volume(&self) -> f32 {
+        self.master * self.sfx
+    }
+}
+
+#[derive(Resource, Default)]
+struct FrameLimit {
+    fps_cap: Option<u32>,
+}
+
+#[derive(Resource)]
+struct RunAssists {
+    lowest_game_speed: u32,
+    highest_basket_scale: u32,
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Endless,
+    TimeTrial,
+    Weekly,
+    Timed,
+}
+
+impl GameMode {
+    // Time trials punish misses with a time penalty and timed runs simply end,
+    // so only the open-ended modes track lives.
+    fn has_lives(self) -> bool {
+        matches!(self, GameMode::Endless | GameMode::Weekly)
+    }
+}
+
+#[derive(Resource)]
+pub struct Scoreboard {
+    pub score: i32,
+    combo: u32,
+    fractional_points: f32,
+}
+
+#[derive(Resource)]
+struct Lives(u32);
+
+#[derive(Event)]
+struct ComboMilestoneEvent {
+    combo: u32,
+}
+
+#[derive(Event)]
+struct AppleCaughtEvent {
+    position: Vec3,
+    points: i32,
+    // Landed near the middle of the basket.
+    perfect: bool,
+}
+
+#[derive(Event)]
+struct AppleMissedEvent {
+    position: Vec3,
+}
+
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+pub enum PowerUp {
+    Rewind,
+    Widen,
+    SlowMotion,
+    Star,
+}
+
+impl PowerUp {
+    fn tint(self) -> Color {
+        match self {
+            PowerUp::Rewind => Color::srgb(0.4, 0.8, 1.),
+            PowerUp::Widen => Color::srgb(0.5, 1., 0.4),
+            PowerUp::SlowMotion => Color::srgb(0.8, 0.5, 1.),
+            PowerUp::Star => Color::srgb(1., 1., 0.5),
+        }
+    }
+}
+
+#[derive(Event)]
+struct PowerUpCollectedEvent(PowerUp);
+
+#[derive(Resource)]
+struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+// Randomness that only changes how things look or sound. It is kept apart
+// from `GameRng` because it is drawn per frame, so a run plays out the same
+// whatever the frame rate.
+#[derive(Resource)]
+struct CosmeticRng(StdRng);
+
+impl Default for CosmeticRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+#[derive(Resource, Default)]
+struct RunStats {
+    catch_xs: Vec<f32>,
+    miss_xs: Vec<f32>,
+    miss_times: Vec<f32>,
+    // Run times at which lives were lost. A bomb takes every remaining life
+    // at once and is recorded once.
+    life_lost_times: Vec<f32>,
+    // Run times at which golden frenzies began.
+    frenzy_times: Vec<f32>,
+    score_samples: Vec<u32>,
+    elapsed: f32,
+    best_combo: u32,
+    new_combo_record: bool,
+    // Rotten apples and bombs that fell past the basket.
+    hazards_avoided: u32,
+}
+
+// What a viewer asked to drop. Only Twitch chat asks for golden apples and bombs.
+#[cfg_attr(not(feature = "twitch"), allow(dead_code))]
+#[derive(Clone, Copy, Serialize, Deserialize)]
+enum RequestedDrop {
+    Apple,
+    Golden,
+    Bomb,
+}
+
+impl RequestedDrop {
+    fn label(self) -> &'static str {
+        match self {
+            RequestedDrop::Apple => "an apple",
+            RequestedDrop::Golden => "a golden apple",
+            RequestedDrop::Bomb => "a bomb",
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct SpawnRequest {
+    viewer: String,
+    drop: RequestedDrop,
+}
+
+#[derive(Resource, Default)]
+struct SpawnQueue(VecDeque<SpawnRequest>);
+
+const DIFFICULTY_RAMP_SECONDS: f32 = 180.;
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+pub struct DifficultySettings {
+    start_spawn_interval: f32,
+    end_spawn_interval: f32,
+    // How hard apples are pulled down, in pixels per second squared.
+    start_gravity: f32,
+    end_gravity: f32,
+    // Apples dropped together on each spawn tick.
+    start_spawn_count: u32,
+    end_spawn_count: u32,
+    lives: u32,
+}
+
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+#[derive(Resource)]
+struct DifficultyCurve {
+    settings: DifficultySettings,
+    elapsed: f32,
+    pinned_interval: Option<f32>,
+}
+
+impl DifficultyCurve {
+    fn new(settings: DifficultySettings) -> Self {
+        Self {
+            settings,
+            elapsed: 0.,
+            pinned_interval: None,
+        }
+    }
+
+    fn progress(&self) -> f32 {
+        (self.elapsed / DIFFICULTY_RAMP_SECONDS).min(1.)
+    }
+
+    fn spawn_interval(&self) -> f32 {
+        let DifficultySettings {
+            start_spawn_interval: start,
+            end_spawn_interval: end,
+            ..
+        } = self.settings;
+        self.pinned_interval
+            .unwrap_or_else(|| start + (end - start) * self.progress())
+    }
+
+    fn gravity(&self) -> f32 {
+        let DifficultySettings {
+            start_gravity: start,
+            end_gravity: end,
+            ..
+        } = self.settings;
+        start + (end - start) * self.progress()
+    }
+
+    // The most apples a spawn tick can drop grows with the curve.
+    fn spawn_count(&self) -> RangeInclusive<u32> {
+        let DifficultySettings {
+            start_spawn_count: start,
+            end_spawn_count: end,
+            ..
+        } = self.settings;
+        let start = start.max(1);
+        let most = start as f32 + end.saturating_sub(start) as f32 * self.progress();
+        start..=most.round() as u32
+    }
+}
+
+// Follows the difficulty curve, so apples that are already falling speed up
+// along with it.
+#[derive(Resource, Default)]
+struct Gravity {
+    acceleration: f32,
+    terminal_speed: f32,
+}
+
+// Score-based levels stack on top of the time-based curve.
+#[derive(Resource)]
+struct Level(u32);
+
+impl Default for Level {
+    fn default() -> Self {
+        Level(1)
+    }
+}
+
+impl Level {
+    const POINTS_PER_LEVEL: i32 = 10;
+    // Levels keep counting up, but the game stops getting harder after this many.
+    const MAX_STEPS: u32 = 10;
+
+    fn for_score(score: i32) -> u32 {
+        (score.max(0) / Self::POINTS_PER_LEVEL) as u32 + 1
+    }
+
+    fn steps(&self) -> i32 {
+        (self.0 - 1).min(Self::MAX_STEPS) as i32
+    }
+
+    fn spawn_interval_scale(&self) -> f32 {
+        0.95_f32.powi(self.steps())
+    }
+
+    fn fall_speed_scale(&self) -> f32 {
+        1.04_f32.powi(self.steps())
+    }
+}
+
+// Slows apples and the spawn rhythm without touching `Time`, so the basket
+// keeps its full speed.
+#[derive(Resource)]
+struct TimeDilation {
+    timer: Timer,
+}
+
+impl Default for TimeDilation {
+    fn default() -> Self {
+        Self {
+            timer: Timer::new(Duration::ZERO, TimerMode::Once),
+        }
+    }
+}
+
+impl TimeDilation {
+    const SLOW_MOTION_SCALE: f32 = 0.5;
+
+    fn is_active(&self) -> bool {
+        !self.timer.finished()
+    }
+
+    fn scale(&self) -> f32 {
+        if self.is_active() {
+            Self::SLOW_MOTION_SCALE
+        } else {
+            1.
+        }
+    }
+
+    // Overlapping pickups add to whatever time is left.
+    fn extend(&mut self, duration: Duration) {
+        let remaining = if self.is_active() {
+            self.timer.remaining()
+        } else {
+            Duration::ZERO
+        };
+        self.timer = Timer::new(remaining + duration, TimerMode::Once);
+    }
+}
+
+#[derive(Resource)]
+struct Multiplier {
+    factor: u32,
+    timer: Timer,
+}
+
+impl Default for Multiplier {
+    fn default() -> Self {
+        Self {
+            factor: 1,
+            timer: Timer::new(Duration::ZERO, TimerMode::Once),
+        }
+    }
+}
+
+impl Multiplier {
+    const MAX_FACTOR: u32 = 4;
+    const DURATION: Duration = Duration::from_secs(10);
+
+    // The first star doubles points and each one after that adds one more,
+    // always restarting the full duration.
+    fn bump(&mut self) {
+        self.factor = (self.factor + 1).min(Self::MAX_FACTOR);
+        self.timer = Timer::new(Self::DURATION, TimerMode::Once);
+    }
+
+    fn tick(&mut self, delta: Duration) {
+        self.timer.tick(delta);
+        if self.timer.just_finished() {
+            self.factor = 1;
+        }
+    }
+}
+
+#[derive(Resource)]
+struct AppleSpawnerConfig {
+    timer: Timer,
+    recent_xs: VecDeque<f32>,
+    spacing: f32,
+    history_len: usize,
+    max_attempts: u32,
+    spawn_count: RangeInclusive<u32>,
+    // Apples dropped on the same tick stay this many widths apart.
+    group_spacing: f32,
+    gold_rush: bool,
+    apples_dropped: u32,
+    frenzy: bool,
+}
+
+impl AppleSpawnerConfig {
+    fn new(interval: f32) -> Self {
+        Self {
+            timer: Timer::new(Duration::from_secs_f32(interval), TimerMode::Repeating),
+            recent_xs: VecDeque::new(),
+            spacing: 1.,
+            history_len: 2,
+            max_attempts: 8,
+            spawn_count: 1..=1,
+            group_spacing: 2.,
+            gold_rush: false,
+            apples_dropped: 0,
+            frenzy: false,
+        }
+    }
+
+    fn next_is_golden(&mut self) -> bool {
+        self.apples_dropped += 1;
+        self.frenzy
+            || self.gold_rush
+                && self
+                    .apples_dropped
+                    .is_multiple_of(cheats::GOLD_RUSH_INTERVAL)
+    }
+
+    fn pick_spawn_x(&mut self, rng: &mut impl Rng, range: f32, apple_width: f32) -> f32 {
+        let min_distance = apple_width * self.spacing;
+        let mut x = rng.gen_range(-range..=range);
+        for _ in 1..self.max_attempts {
+            if self
+                .recent_xs
+                .iter()
+                .all(|recent| (x - recent).abs() >= min_distance)
+            {
+                break;
+            }
+            x = rng.gen_range(-range..=range);
+        }
+
+        self.remember(x);
+        x
+    }
+
+    // Each apple of a group gets its own slice of the play area, so they never
+    // overlap. A play area too narrow for the whole group drops fewer.
+    fn pick_spawn_xs(&mut self, rng: &mut impl Rng, range: f32, apple_width: f32) -> Vec<f32> {
+        let gap = apple_width * self.group_spacing;
+        let fits = ((2. * range / gap) as u32).max(1);
+        let count = rng.gen_range(self.spawn_count.clone()).clamp(1, fits);
+        if count == 1 {
+            return vec![self.pick_spawn_x(rng, range, apple_width)];
+        }
+        let slice = 2. * range / count as f32;
+        let xs: Vec<f32> = (0..count)
+            .map(|i| {
+                let left = -range + slice * i as f32 + gap / 2.;
+                rng.gen_range(left..=left + slice - gap)
+            })
+            .collect();
+        for &x in &xs {
+            self.remember(x);
+        }
+        xs
+    }
+
+    // Like `pick_spawn_xs` but limited to the tree's branches. Neighbouring
+    // branches can be too close to drop from together, so a group may come out
+    // smaller than rolled.
+    fn pick_branches(
+        &mut self,
+        rng: &mut impl Rng,
+        branches: &[Vec2],
+        apple_width: f32,
+    ) -> Vec<Vec2> {
+        let count = rng.gen_range(self.spawn_count.clone()).max(1) as usize;
+        let gap = apple_width * self.group_spacing;
+        let min_distance = apple_width * self.spacing;
+        let mut candidates = branches.to_vec();
+        candidates.shuffle(rng);
+        // Branches that just dropped an apple are only used when nothing else fits.
+        candidates.sort_by_key(|branch| {
+            self.recent_xs
+                .iter()
+                .any(|recent| (branch.x - recent).abs() < min_distance)
+        });
+        let mut picked: Vec<Vec2> = Vec::new();
+        for branch in candidates {
+            if picked.len() == count {
+                break;
+            }
+            if picked.iter().all(|other| (other.x - branch.x).abs() >= gap) {
+                picked.push(branch);
+            }
+        }
+        for branch in &picked {
+            self.remember(branch.x);
+        }
+        picked
+    }
+
+    fn remember(&mut self, x: f32) {
+        self.recent_xs.push_back(x);
+        while self.recent_xs.len() > self.history_len {
+            self.recent_xs.pop_front();
+        }
+    }
+}
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
+
+const COMBO_MILESTONES: [u32; 4] = [5, 10, 20, 40];
+const MAX_FRAME_DELTA: Duration = Duration::from_nanos(1_000_000_000 / 15);
+// Wide enough for the biggest basket: 150% assist size, the big basket cheat
+// and a widen pickup all together.
+const MIN_WINDOW_SIZE: Vec2 = Vec2::new(480., 360.);
+// A browser tab can't be closed by the game, so the web build has no quit buttons.
+const CAN_QUIT: bool = cfg!(not(target_arch = "wasm32"));
+
+pub fn run() {
+    App::new()
+        .add_plugins(
+            DefaultPlugins
+                .set(ImagePlugin::default_nearest())
+                .set(WindowPlugin {
+                    primary_window: Some(Window {
+                        title: "Applecatcher".to_string(),
+                        present_mode: PresentMode::AutoNoVsync,
+                        resize_constraints: WindowResizeConstraints {
+                            min_width: MIN_WINDOW_SIZE.x,
+                            min_height: MIN_WINDOW_SIZE.y,
+                            ..default()
+                        },
+                        #[cfg(target_arch = "wasm32")]
+                        canvas: Some("#applecatcher".to_string()),
+                        #[cfg(target_arch = "wasm32")]
+                        fit_canvas_to_parent: true,
+                        ..default()
+                    }),
+                    ..default()
+                }),
+        )
+        .add_plugins(applecatcher_plugin)
+        .add_loading_state(
+            LoadingState::new(GameState::Loading)
+                .continue_to_state(GameState::Loaded)
+                .on_failure_continue_to_state(GameState::LoadError)
+                .load_collection::<ImageAssets>()
+                .load_collection::<FontAssets>()
+                .load_collection::<waves::WaveAssets>()
+                .load_collection::<audio::AudioAssets>(),
+        )
+        .add_plugins((
+            #[cfg(not(target_arch = "wasm32"))]
+            screenshot::screenshot_plugin,
+            #[cfg(feature = "debug_overlay")]
+            debug_overlay::debug_overlay_plugin,
+        ))
+        .run();
+}
+
+/// The whole game apart from the engine's own plugins and the asset loading,
+/// so it also runs headless on `MinimalPlugins` with the assets inserted
+/// directly.
+pub fn applecatcher_plugin(app: &mut App) {
+    app.insert_resource(Time::<Fixed>::from_hz(60.0))
+        .insert_resource(Time::<Virtual>::from_max_delta(MAX_FRAME_DELTA))
+        .init_state::<GameState>()
+        .add_sub_state::<PauseMode>()
+        .init_state::<SettingsMenu>()
+        .add_systems(Startup, setup)
+        .add_systems(PreUpdate, sync_play_area)
+        .add_systems(Update, fit_background.run_if(resource_changed::<PlayArea>))
+        .add_systems(OnEnter(GameState::Loaded), setup_background)
+        .add_plugins((
+            main_menu::main_menu_plugin,
+            game::game_plugin,
+            pause_menu::pause_menu_plugin,
+            announcer::announcer_plugin,
+            interpolation::interpolation_plugin,
+            time_trial::time_trial_plugin,
+            results::results_plugin,
+            settings_menu::settings_menu_plugin,
+            assists::assists_plugin,
+            stats::stats_plugin,
+            persistence::persistence_plugin,
+            stats_screen::stats_screen_plugin,
+            achievements::achievements_plugin,
+            customize_screen::customize_screen_plugin,
+            frame_limiter::frame_limiter_plugin,
+        ))
+        .add_plugins((
+            textures::textures_plugin,
+            load_error::load_error_plugin,
+            pause_snapshot::pause_snapshot_plugin,
+            basket_stack::basket_stack_plugin,
+            music::music_plugin,
+            actions::actions_plugin,
+            touch_controls::touch_controls_plugin,
+            accessibility::accessibility_plugin,
+            rewind::rewind_plugin,
+            grading::grading_plugin,
+            waves::waves_plugin,
+            cheats::cheats_plugin,
+            monitors::monitors_plugin,
+            weekly::weekly_plugin,
+            wind::wind_plugin,
+        ))
+        .add_plugins((
+            game_over::game_over_plugin,
+            menu_focus::menu_focus_plugin,
+            timed::timed_plugin,
+            controls_menu::controls_menu_plugin,
+            audio::audio_plugin,
+            audio_menu::audio_menu_plugin,
+            score_popups::score_popups_plugin,
+            camera_shake::camera_shake_plugin,
+            parallax::parallax_plugin,
+            countdown::countdown_plugin,
+            config::config_plugin,
+            replay::replay_plugin,
+            birds::birds_plugin,
+        ))
+        .add_plugins((
+            toasts::toasts_plugin,
+            frenzy::frenzy_plugin,
+            lit_bomb::lit_bomb_plugin,
+            #[cfg(feature = "twitch")]
+            twitch::twitch_plugin,
+            #[cfg(feature = "dev")]
+            dev_console::dev_console_plugin,
+        ))
+        .init_resource::<GameMode>()
+        .init_resource::<Difficulty>()
+        .init_resource::<MotionPreferences>()
+        .init_resource::<AssistSettings>()
+        .init_resource::<FrameLimit>()
+        .init_resource::<ShowMagnetAura>()
+        .init_resource::<TextureQuality>()
+        .init_resource::<TouchControls>()
+        .init_resource::<ControlScheme>()
+        .init_resource::<MovementStyle>()
+        .init_resource::<ActionState>()
+        .init_resource::<CosmeticRng>()
+        .init_resource::<PlayArea>();
+}
+
+#[derive(Component)]
+struct MainCamera;
+
+/// The size of the playfield in world units. Gameplay reads this rather than the
+/// window so the simulation does not depend on one existing.
+#[derive(Resource, Clone, Copy)]
+pub struct PlayArea {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for PlayArea {
+    fn default() -> Self {
+        let resolution = WindowResolution::default();
+        Self {
+            width: resolution.width(),
+            height: resolution.height(),
+        }
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((Camera2dBundle::default(), MainCamera));
+}
+
+fn sync_play_area(
+    mut resized_events: EventReader<WindowResized>,
+    windows: Query<(), With<PrimaryWindow>>,
+    mut play_area: ResMut<PlayArea>,
+) {
+    for event in resized_events.read() {
+        if windows.contains(event.window) {
+            play_area.width = event.width;
+            play_area.height = event.height;
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn os_prefers_reduced_motion() -> bool {
+    std::process::Command::new("gsettings")
+        .args(["get", "org.gnome.desktop.interface", "enable-animations"])
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim() == "false")
+        .unwrap_or(false)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn os_prefers_reduced_motion() -> bool {
+    false
+}
+
+fn start_run(
+    commands: &mut Commands,
+    config: &GameConfig,
+    game_mode: GameMode,
+    difficulty: Difficulty,
+) {
+    let settings = config.run_settings(game_mode, difficulty);
+    commands.insert_resource(game_mode);
+    commands.insert_resource(Scoreboard {
+        score: 0,
+        combo: 0,
+        fractional_points: 0.,
+    });
+    commands.insert_resource(Lives(settings.lives));
+    commands.insert_resource(AppleSpawnerConfig::new(settings.start_spawn_interval));
+    commands.insert_resource(DifficultyCurve::new(settings));
+    commands.insert_resource(Gravity::default());
+    commands.insert_resource(TimeDilation::default());
+    commands.insert_resource(Level::default());
+    commands.insert_resource(Multiplier::default());
+    commands.insert_resource(SpawnQueue::default());
+    commands.insert_resource(waves::WavePlayer::default());
+    commands.insert_resource(RunAssists {
+        lowest_game_speed: 100,
+        highest_basket_scale: 100,
+    });
+    commands.insert_resource(RunStats::default());
+    // Everyone plays the same weekly run, whatever seed the config asks for.
+    let seed = match game_mode {
+        GameMode::Weekly => {
+            let week = weekly::WeekId::current();
+            commands.insert_resource(weekly::WeeklyRun(week));
+            week.seed()
+        }
+        _ => {
+            commands.remove_resource::<weekly::WeeklyRun>();
+            config.seed.unwrap_or_else(rand::random)
+        }
+    };
+    commands.insert_resource(GameRng::from_seed(seed));
+}
+
+fn setup_background(
+    mut commands: Commands,
+    textures: Textures,
+    play_area: Res<PlayArea>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if let Some((texture, _)) = textures.sprite(Texture::Background) {
+        commands
+            .spawn(SpriteBundle {
+                transform: Transform::from_translation(Vec3::new(0., 0., -1.)),
+                texture,
+                sprite: Sprite {
+                    custom_size: Some(Vec2::new(play_area.width, play_area.height)),
+                    ..default()
+                },
+                ..default()
+            })
+            .insert(Background);
+    }
+
+    game_state.set(GameState::MainMenu);
+}
+
+// The backdrop stretches to cover the whole play area.
+fn fit_background(play_area: Res<PlayArea>, mut query: Query<&mut Sprite, With<Background>>) {
+    for mut sprite in &mut query {
+        sprite.custom_size = Some(Vec2::new(play_area.width, play_area.height));
+    }
+}
+
+pub mod main_menu;
+
+pub mod game;
+
+pub mod pause_menu;
+
+mod announcer;
+
+mod time_trial;
+
+mod game_over;
+
+mod timed;
+
+mod results;
+
+mod settings_menu;
+
+mod controls_menu;
+
+mod audio_menu;
+
+mod frame_limiter;
+
+mod assists;
+
+mod stats;
+
+pub mod persistence;
+
+mod stats_screen;
+
+mod achievements;
+
+mod cosmetics;
+
+mod customize_screen;
+
+pub mod interpolation;
+
+pub mod textures;
+
+mod load_error;
+
+mod score_popups;
+
+mod parallax;
+
+mod countdown;
+
+mod camera_shake;
+
+mod basket_stack;
+
+pub mod audio;
+
+pub mod music;
+
+pub mod actions;
+
+mod menu_focus;
+
+mod touch_controls;
+
+mod accessibility;
+
+#[cfg(feature = "twitch")]
+mod twitch;
+
+mod replay;
+
+mod replay_file;
+
+mod rewind;
+
+mod grading;
+
+mod cheats;
+
+mod toasts;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod screenshot;
+
+#[cfg(feature = "debug_overlay")]
+mod debug_overlay;
+
+mod monitors;
+
+mod weekly;
+
+mod frenzy;
+
+mod lit_bomb;
+
+pub mod config;
+
+pub mod waves;
+
+mod wind;
+
+mod birds;
+
+#[cfg(feature = "dev")]
+mod dev_console;
+
+mod pause_snapshot;
+
+fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
+    for entity in &to_despawn {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APPLE_WIDTH: f32 = 64.;
+
+    #[test]
+    fn spawns_keep_clear_of_the_last_two() {
+        let mut game_rng = GameRng::from_seed(7);
+        let mut spawner = AppleSpawnerConfig::new(1.);
+        let mut xs = Vec::new();
+        for _ in 0..200 {
+            xs.push(spawner.pick_spawn_x(&mut game_rng.rng, 576., APPLE_WIDTH));
+        }
+        for (i, x) in xs.iter().enumerate().skip(2) {
+            for recent in &xs[i - 2..i] {
+                assert!((x - recent).abs() >= APPLE_WIDTH, "spawn {i} at {x}");
+            }
+        }
+    }
+
+    #[test]
+    fn a_play_area_too_narrow_to_space_out_still_spawns() {
+        let mut game_rng = GameRng::from_seed(7);
+        let mut spawner = AppleSpawnerConfig::new(1.);
+        for _ in 0..20 {
+            let x = spawner.pick_spawn_x(&mut game_rng.rng, 10., APPLE_WIDTH);
+            assert!((-10. ..=10.).contains(&x));
+        }
+    }
+
+    #[test]
+    fn the_gold_rush_makes_every_third_apple_golden() {
+        let mut spawner = AppleSpawnerConfig::new(1.);
+        assert!(!(0..6).any(|_| spawner.next_is_golden()));
+        spawner.gold_rush = true;
+        let golden: Vec<bool> = (0..6).map(|_| spawner.next_is_golden()).collect();
+        assert_eq!(golden, [false, false, true, false, false, true]);
+    }
+
+    #[test]
+    fn a_frenzy_makes_every_apple_golden() {
+        let mut spawner = AppleSpawnerConfig::new(1.);
+        spawner.frenzy = true;
+        assert!((0..6).all(|_| spawner.next_is_golden()));
+    }
+}
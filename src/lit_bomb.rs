@@ -0,0 +1,251 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{Action, InputBindings},
+    audio::{play_sfx, AudioAssets},
+    controls_menu::key_name,
+    game::{GameTick, OnGameScreen, Player, SpriteSize},
+    interpolation::PreviousTransform,
+    replay::TickInput,
+    textures::{Texture, Textures},
+    AudioSettings, GameState, Lives, PauseMode, PlayArea, RunStats,
+};
+
+const FUSE_DURATION: f32 = 1.5;
+const DEFUSE_PRESSES: u32 = 3;
+const FLASH_INTERVAL: f32 = 0.15;
+const LIT_BOMB_SCALE: f32 = 0.4;
+const LIT_BOMB_TINT: Color = Color::srgb(1., 0.3, 0.2);
+const THROW_SPEED: f32 = 900.;
+const PROMPT_COLOR: Color = Color::srgb(1., 0.6, 0.2);
+
+// A bomb that landed in the basket. Pressing the defuse key enough times
+// before the fuse runs out throws it away, otherwise it costs a life.
+#[derive(Component, Clone)]
+pub struct LitBomb {
+    fuse: Timer,
+    presses: u32,
+}
+
+impl Default for LitBomb {
+    fn default() -> Self {
+        Self {
+            fuse: Timer::from_seconds(FUSE_DURATION, TimerMode::Once),
+            presses: 0,
+        }
+    }
+}
+
+// The bomb drawn sitting on the basket while its fuse burns.
+#[derive(Component)]
+struct LitBombSprite;
+
+#[derive(Component)]
+struct ThrownBomb;
+
+#[derive(Component)]
+struct DefusePrompt;
+
+pub fn lit_bomb_plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        burn_fuses.after(GameTick).run_if(
+            in_state(GameState::Game)
+                .and_then(in_state(PauseMode::Playing))
+                .and_then(any_with_component::<Player>),
+        ),
+    )
+    // Moved on the fixed tick like everything else that falls, so it is
+    // interpolated between ticks.
+    .add_systems(
+        FixedUpdate,
+        fly_thrown_bombs
+            .after(GameTick)
+            .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+    )
+    .add_systems(
+        Update,
+        (show_lit_bomb, update_defuse_prompt)
+            .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn burn_fuses(
+    mut commands: Commands,
+    time: Res<Time>,
+    tick_input: Res<TickInput>,
+    textures: Textures,
+    mut player_query: Query<(Entity, &Transform, &mut LitBomb), With<Player>>,
+    mut lives: ResMut<Lives>,
+    mut run_stats: ResMut<RunStats>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (player, transform, mut lit_bomb) in &mut player_query {
+        if tick_input.defuse {
+            lit_bomb.presses += 1;
+        }
+        if lit_bomb.presses >= DEFUSE_PRESSES {
+            commands.entity(player).remove::<LitBomb>();
+            if let Some((texture, size)) = textures.sprite(Texture::Bomb) {
+                commands.spawn((
+                    SpriteBundle {
+                        transform: Transform::from_translation(transform.translation)
+                            .with_scale(Vec3::splat(LIT_BOMB_SCALE)),
+                        texture,
+                        sprite: Sprite {
+                            custom_size: Some(size),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    PreviousTransform::new(transform.translation),
+                    ThrownBomb,
+                    OnGameScreen,
+                ));
+            }
+            continue;
+        }
+        if lit_bomb.fuse.tick(time.delta()).finished() {
+            commands.entity(player).remove::<LitBomb>();
+            let elapsed = run_stats.elapsed;
+            run_stats.life_lost_times.push(elapsed);
+            lives.0 = lives.0.saturating_sub(1);
+            if lives.0 == 0 {
+                game_state.set(GameState::GameOver);
+            }
+        }
+    }
+}
+
+// Flashes the bomb on the basket and beeps with every flash.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn show_lit_bomb(
+    mut commands: Commands,
+    textures: Textures,
+    audio_assets: Option<Res<AudioAssets>>,
+    audio_settings: Res<AudioSettings>,
+    player_query: Query<(&Transform, &SpriteSize, Option<&LitBomb>), With<Player>>,
+    mut sprite_query: Query<
+        (Entity, &mut Transform, &mut Visibility),
+        (With<LitBombSprite>, Without<Player>),
+    >,
+    mut was_shown: Local<bool>,
+) {
+    let lit = player_query
+        .get_single()
+        .ok()
+        .and_then(|(transform, size, lit_bomb)| Some((transform, size, lit_bomb?)));
+    let Some((player_transform, player_size, lit_bomb)) = lit else {
+        for (entity, ..) in &sprite_query {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    let translation = player_transform.translation
+        + Vec3::new(0., player_size.0.y * player_transform.scale.y / 2., 0.1);
+    let shown = ((lit_bomb.fuse.elapsed_secs() / FLASH_INTERVAL) as u32).is_multiple_of(2);
+    if shown && !*was_shown {
+        if let Some(audio_assets) = &audio_assets {
+            play_sfx(&mut commands, &audio_assets.click, &audio_settings);
+        }
+    }
+    *was_shown = shown;
+    let visibility = if shown {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+
+    if let Ok((_, mut transform, mut sprite_visibility)) = sprite_query.get_single_mut() {
+        transform.translation = translation;
+        *sprite_visibility = visibility;
+        return;
+    }
+    let Some((texture, size)) = textures.sprite(Texture::Bomb) else {
+        return;
+    };
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_translation(translation)
+                .with_scale(Vec3::splat(LIT_BOMB_SCALE)),
+            texture,
+            sprite: Sprite {
+                custom_size: Some(size),
+                color: LIT_BOMB_TINT,
+                ..default()
+            },
+            visibility,
+            ..default()
+        },
+        LitBombSprite,
+        OnGameScreen,
+    ));
+}
+
+fn update_defuse_prompt(
+    mut commands: Commands,
+    bindings: Res<InputBindings>,
+    player_query: Query<&LitBomb, With<Player>>,
+    mut prompt_query: Query<(Entity, &mut Text), With<DefusePrompt>>,
+) {
+    let Ok(lit_bomb) = player_query.get_single() else {
+        for (entity, _) in &prompt_query {
+            commands.entity(entity).despawn();
+        }
+        return;
+    };
+    let key = bindings
+        .keys(Action::Defuse)
+        .first()
+        .map_or_else(|| "Defuse".to_string(), |&key| key_name(key));
+    let progress: String = (0..DEFUSE_PRESSES)
+        .map(|press| {
+            if press < lit_bomb.presses {
+                '■'
+            } else {
+                '□'
+            }
+        })
+        .collect();
+    let label = format!("Press {key}! {progress}");
+
+    if let Ok((_, mut text)) = prompt_query.get_single_mut() {
+        text.sections[0].value = label;
+        return;
+    }
+    commands.spawn((
+        TextBundle::from_section(
+            label,
+            TextStyle {
+                font_size: 36.,
+                color: PROMPT_COLOR,
+                ..default()
+            },
+        )
+        .with_text_justify(JustifyText::Center)
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            bottom: Val::Percent(30.),
+            ..default()
+        }),
+        DefusePrompt,
+        OnGameScreen,
+    ));
+}
+
+fn fly_thrown_bombs(
+    mut commands: Commands,
+    time: Res<Time>,
+    play_area: Res<PlayArea>,
+    mut bomb_query: Query<(Entity, &mut Transform), With<ThrownBomb>>,
+) {
+    for (entity, mut transform) in &mut bomb_query {
+        transform.translation.y += THROW_SPEED * time.delta_seconds();
+        transform.rotate_z(10. * time.delta_seconds());
+        if transform.translation.y > play_area.height {
+            commands.entity(entity).despawn();
+        }
+    }
+}
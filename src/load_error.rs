@@ -0,0 +1,243 @@
+use bevy::{asset::AssetLoadFailedEvent, prelude::*};
+
+use crate::{
+    despawn_screen, parallax::PARALLAX_DIR, textures::HD_TEXTURE_DIR, waves::Wave, GameState,
+    CAN_QUIT, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+#[derive(Component)]
+struct OnLoadErrorScreen;
+
+#[derive(Component)]
+enum MenuButtonAction {
+    Retry,
+    Quit,
+}
+
+#[derive(Resource, Default)]
+struct LoadErrors(Vec<String>);
+
+pub fn load_error_plugin(app: &mut App) {
+    app.init_resource::<LoadErrors>()
+        .add_systems(OnEnter(GameState::Loading), clear_errors)
+        .add_systems(Update, record_failures)
+        .add_systems(OnEnter(GameState::LoadError), setup)
+        .add_systems(
+            Update,
+            (button_system, menu_action, refresh_on_new_errors)
+                .run_if(in_state(GameState::LoadError)),
+        )
+        .add_systems(
+            OnExit(GameState::LoadError),
+            despawn_screen::<OnLoadErrorScreen>,
+        );
+}
+
+fn clear_errors(mut errors: ResMut<LoadErrors>) {
+    errors.0.clear();
+}
+
+fn record_failures(
+    mut image_failures: EventReader<AssetLoadFailedEvent<Image>>,
+    mut wave_failures: EventReader<AssetLoadFailedEvent<Wave>>,
+    mut audio_failures: EventReader<AssetLoadFailedEvent<AudioSource>>,
+    mut errors: ResMut<LoadErrors>,
+) {
+    for event in image_failures.read() {
+        // HD textures are optional and fall back to the standard set, and
+        // missing parallax layers are simply not drawn.
+        let path = event.path.path();
+        if path.starts_with(HD_TEXTURE_DIR) || path.starts_with(PARALLAX_DIR) {
+            continue;
+        }
+        errors.0.push(format!("{}: {}", event.path, event.error));
+    }
+    for event in wave_failures.read() {
+        errors.0.push(format!("{}: {}", event.path, event.error));
+    }
+    for event in audio_failures.read() {
+        errors.0.push(format!("{}: {}", event.path, event.error));
+    }
+}
+
+fn setup(mut commands: Commands, errors: Res<LoadErrors>) {
+    spawn_screen(&mut commands, &errors);
+}
+
+fn refresh_on_new_errors(
+    mut commands: Commands,
+    errors: Res<LoadErrors>,
+    screen_query: Query<Entity, With<OnLoadErrorScreen>>,
+) {
+    if !errors.is_changed() {
+        return;
+    }
+    for entity in &screen_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_screen(&mut commands, &errors);
+}
+
+fn spawn_screen(commands: &mut Commands, errors: &LoadErrors) {
+    let button_style = Style {
+        width: Val::Px(250.0),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font_size: 40.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnLoadErrorScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(
+                        TextBundle::from_section(
+                            "Failed to load assets",
+                            TextStyle {
+                                font_size: 60.,
+                                ..default()
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::bottom(Val::Px(20.)),
+                            ..default()
+                        }),
+                    );
+
+                    if errors.0.is_empty() {
+                        parent.spawn(TextBundle::from_section(
+                            "An asset could not be loaded.",
+                            TextStyle {
+                                font_size: 24.,
+                                ..default()
+                            },
+                        ));
+                    }
+                    for error in &errors.0 {
+                        parent.spawn(
+                            TextBundle::from_section(
+                                error.clone(),
+                                TextStyle {
+                                    font_size: 24.,
+                                    color: Color::srgb(1., 0.4, 0.4),
+                                    ..default()
+                                },
+                            )
+                            .with_style(Style {
+                                max_width: Val::Px(900.),
+                                margin: UiRect::bottom(Val::Px(6.)),
+                                ..default()
+                            }),
+                        );
+                    }
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                margin: UiRect::top(Val::Px(20.)),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: button_style.clone(),
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    MenuButtonAction::Retry,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Retry",
+                                        button_text_style.clone(),
+                                    ));
+                                });
+                            if CAN_QUIT {
+                                parent
+                                    .spawn((
+                                        ButtonBundle {
+                                            style: button_style,
+                                            background_color: NORMAL_BUTTON.into(),
+                                            ..default()
+                                        },
+                                        MenuButtonAction::Quit,
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            "Quit",
+                                            button_text_style,
+                                        ));
+                                    });
+                            }
+                        });
+                });
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Retry => game_state.set(GameState::Loading),
+                MenuButtonAction::Quit => {
+                    app_exit_events.send(AppExit::Success);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,721 @@
+use bevy::prelude::*;
+
+use super::{
+    despawn_screen,
+    menu_focus::{navigate, NavigateMenus},
+    persistence::{
+        has_replay, load_imported_replays, load_replay, store_imported_replay, DataDir, HighScore,
+        Profile,
+    },
+    replay::start_playback,
+    replay_file::{export_replay, import_replay, ReplayFileError},
+    start_run,
+    time_trial::format_time,
+    toasts::Toast,
+    weekly::WeekId,
+    Difficulty, GameConfig, GameMode, GameState, SettingsMenu, CAN_QUIT, HOVERED_BUTTON,
+    NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+#[derive(Component)]
+struct OnMainMenuScreen;
+
+// The selected difficulty keeps the pressed colour while it is not hovered.
+fn difficulty_color(choice: Difficulty, selected: Difficulty) -> Color {
+    if choice == selected {
+        PRESSED_BUTTON
+    } else {
+        NORMAL_BUTTON
+    }
+}
+
+fn best_grade_label(profile: &Profile, game_mode: GameMode) -> String {
+    match profile.best_grades.get(&game_mode) {
+        Some(grade) => format!("Best: {}", grade.letter()),
+        None => String::new(),
+    }
+}
+
+const FAILED_COLOR: Color = Color::srgb(1., 0.45, 0.35);
+
+// The menu is rebuilt after an import so the replay buttons match what is
+// stored.
+#[derive(Event)]
+struct ReplayImported;
+
+#[derive(Component)]
+pub enum MenuButtonAction {
+    SetDifficulty(Difficulty),
+    Play,
+    TimeTrial,
+    Timed,
+    Weekly,
+    Settings,
+    Stats,
+    WatchReplay,
+    WatchImported(usize),
+    ExportReplay,
+    ImportReplay,
+    Customize,
+    Quit,
+}
+
+pub fn main_menu_plugin(app: &mut App) {
+    app.add_event::<ReplayImported>()
+        .add_systems(OnEnter(GameState::MainMenu), setup)
+        .add_systems(
+            Update,
+            (
+                button_system,
+                menu_action,
+                highlight_difficulty,
+                (despawn_screen::<OnMainMenuScreen>, setup)
+                    .chain()
+                    .run_if(on_event::<ReplayImported>()),
+            )
+                .chain()
+                .run_if(in_state(GameState::MainMenu)),
+        )
+        .add_systems(
+            PreUpdate,
+            navigate::<MenuButtonAction>
+                .in_set(NavigateMenus)
+                .run_if(in_state(GameState::MainMenu).and_then(in_state(SettingsMenu::Closed))),
+        )
+        .add_systems(
+            OnExit(GameState::MainMenu),
+            despawn_screen::<OnMainMenuScreen>,
+        );
+}
+
+fn setup(
+    mut commands: Commands,
+    profile: Res<Profile>,
+    high_score: Res<HighScore>,
+    difficulty: Res<Difficulty>,
+    dir: Res<DataDir>,
+) {
+    let button_style = Style {
+        width: Val::Px(250.0),
+        height: Val::Px(48.0),
+        margin: UiRect::axes(Val::Px(20.0), Val::Px(5.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font_size: 40.0,
+        ..default()
+    };
+    // The empty node on the other side of each mode button mirrors the grade
+    // label so the buttons stay centred.
+    let grade_style = Style {
+        width: Val::Px(110.0),
+        ..default()
+    };
+    let grade_text_style = TextStyle {
+        font_size: 24.0,
+        color: Color::srgb(1., 0.85, 0.2),
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnMainMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text::from_section(
+                            "AppleCatcher",
+                            TextStyle {
+                                font_size: 80.,
+                                ..default()
+                            },
+                        )
+                        .with_justify(JustifyText::Center),
+                        style: Style {
+                            margin: UiRect::bottom(Val::Px(10.)),
+                            ..default()
+                        },
+                        ..default()
+                    });
+
+                    if high_score.score > 0 {
+                        parent.spawn(TextBundle::from_section(
+                            format!("Best: {}", high_score.score),
+                            TextStyle {
+                                font_size: 30.,
+                                ..default()
+                            },
+                        ));
+                    }
+
+                    if let Some(best_time) = profile.best_time {
+                        parent.spawn(TextBundle::from_section(
+                            format!("Best time: {}", format_time(best_time)),
+                            TextStyle {
+                                font_size: 30.,
+                                ..default()
+                            },
+                        ));
+                    }
+
+                    if profile.prestige_level > 0 {
+                        parent.spawn(TextBundle::from_section(
+                            format!("Prestige {}", profile.prestige_level),
+                            TextStyle {
+                                font_size: 30.,
+                                color: Color::srgb(1., 0.85, 0.2),
+                                ..default()
+                            },
+                        ));
+                    }
+
+                    parent.spawn(NodeBundle::default()).with_children(|parent| {
+                        for choice in Difficulty::ALL {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            width: Val::Px(120.0),
+                                            height: Val::Px(40.0),
+                                            margin: UiRect::all(Val::Px(6.0)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..default()
+                                        },
+                                        background_color: difficulty_color(choice, *difficulty)
+                                            .into(),
+                                        ..default()
+                                    },
+                                    MenuButtonAction::SetDifficulty(choice),
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        choice.name(),
+                                        TextStyle {
+                                            font_size: 26.0,
+                                            ..default()
+                                        },
+                                    ));
+                                });
+                        }
+                    });
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(NodeBundle {
+                                style: grade_style.clone(),
+                                ..default()
+                            });
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: button_style.clone(),
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    MenuButtonAction::Play,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "New Game",
+                                        button_text_style.clone(),
+                                    ));
+                                });
+                            parent.spawn(
+                                TextBundle::from_section(
+                                    best_grade_label(&profile, GameMode::Endless),
+                                    grade_text_style.clone(),
+                                )
+                                .with_style(grade_style.clone()),
+                            );
+                        });
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(NodeBundle {
+                                style: grade_style.clone(),
+                                ..default()
+                            });
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: button_style.clone(),
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    MenuButtonAction::TimeTrial,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Time Trial",
+                                        button_text_style.clone(),
+                                    ));
+                                });
+                            parent.spawn(
+                                TextBundle::from_section(
+                                    best_grade_label(&profile, GameMode::TimeTrial),
+                                    grade_text_style.clone(),
+                                )
+                                .with_style(grade_style.clone()),
+                            );
+                        });
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(NodeBundle {
+                                style: grade_style.clone(),
+                                ..default()
+                            });
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: button_style.clone(),
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    MenuButtonAction::Timed,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "60 Seconds",
+                                        button_text_style.clone(),
+                                    ));
+                                });
+                            parent.spawn(
+                                TextBundle::from_section(
+                                    best_grade_label(&profile, GameMode::Timed),
+                                    grade_text_style.clone(),
+                                )
+                                .with_style(grade_style.clone()),
+                            );
+                        });
+
+                    let week = WeekId::current();
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(NodeBundle {
+                                style: grade_style.clone(),
+                                ..default()
+                            });
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            flex_direction: FlexDirection::Column,
+                                            ..button_style.clone()
+                                        },
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    MenuButtonAction::Weekly,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        "Weekly",
+                                        TextStyle {
+                                            font_size: 32.0,
+                                            ..default()
+                                        },
+                                    ));
+                                    parent.spawn(TextBundle::from_section(
+                                        week.mutator_names(),
+                                        TextStyle {
+                                            font_size: 16.0,
+                                            color: Color::srgb(0.8, 0.8, 0.8),
+                                            ..default()
+                                        },
+                                    ));
+                                });
+                            let best = match profile.weekly_best.get(&week) {
+                                Some(score) => format!("Best: {score}"),
+                                None => String::new(),
+                            };
+                            parent.spawn(
+                                TextBundle::from_section(best, grade_text_style.clone())
+                                    .with_style(grade_style.clone()),
+                            );
+                        });
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Settings,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Settings",
+                                button_text_style.clone(),
+                            ));
+                        });
+
+                    // The replay button shares a row with stats so the menu
+                    // still fits the window.
+                    let replay = if has_replay(&dir) {
+                        load_replay(&dir)
+                    } else {
+                        None
+                    };
+                    let watch_replay = replay.is_some();
+                    let stats_width = if watch_replay {
+                        Val::Px(155.0)
+                    } else {
+                        button_style.width
+                    };
+                    parent.spawn(NodeBundle::default()).with_children(|parent| {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        width: stats_width,
+                                        ..button_style.clone()
+                                    },
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::Stats,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Stats",
+                                    button_text_style.clone(),
+                                ));
+                            });
+                        if watch_replay {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            width: Val::Px(155.0),
+                                            margin: UiRect::axes(Val::Px(5.0), Val::Px(5.0)),
+                                            ..button_style.clone()
+                                        },
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    MenuButtonAction::WatchReplay,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        if replay.as_ref().is_some_and(|replay| replay.imported) {
+                                            "Watch Imported"
+                                        } else {
+                                            "Watch Replay"
+                                        },
+                                        TextStyle {
+                                            font_size: 26.0,
+                                            ..default()
+                                        },
+                                    ));
+                                });
+                        }
+                    });
+
+                    // Exported replays are shared as files, so these are
+                    // smaller than the buttons above.
+                    parent.spawn(NodeBundle::default()).with_children(|parent| {
+                        let mut file_buttons = vec![(MenuButtonAction::ImportReplay, "Import")];
+                        if watch_replay {
+                            file_buttons.push((MenuButtonAction::ExportReplay, "Export"));
+                        }
+                        for (action, label) in file_buttons {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            width: Val::Px(120.0),
+                                            height: Val::Px(34.0),
+                                            margin: UiRect::all(Val::Px(5.0)),
+                                            ..button_style.clone()
+                                        },
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    action,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        label,
+                                        TextStyle {
+                                            font_size: 22.0,
+                                            ..default()
+                                        },
+                                    ));
+                                });
+                        }
+                    });
+
+                    // Replays imported from other players, newest first.
+                    let imported = load_imported_replays(&dir).len();
+                    if imported > 0 {
+                        parent.spawn(NodeBundle::default()).with_children(|parent| {
+                            for index in 0..imported {
+                                parent
+                                    .spawn((
+                                        ButtonBundle {
+                                            style: Style {
+                                                width: Val::Px(120.0),
+                                                height: Val::Px(34.0),
+                                                margin: UiRect::all(Val::Px(5.0)),
+                                                ..button_style.clone()
+                                            },
+                                            background_color: NORMAL_BUTTON.into(),
+                                            ..default()
+                                        },
+                                        MenuButtonAction::WatchImported(index),
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            format!("Imported {}", index + 1),
+                                            TextStyle {
+                                                font_size: 22.0,
+                                                ..default()
+                                            },
+                                        ));
+                                    });
+                            }
+                        });
+                    }
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Customize,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Customize",
+                                button_text_style.clone(),
+                            ));
+                        });
+
+                    if CAN_QUIT {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::Quit,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Quit",
+                                    button_text_style.clone(),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    difficulty: Res<Difficulty>,
+) {
+    for (interaction, mut color, menu_button_action) in &mut interaction_query {
+        *color = match (*interaction, menu_button_action) {
+            (Interaction::Pressed, _) => PRESSED_BUTTON,
+            (Interaction::Hovered, _) => HOVERED_BUTTON,
+            (Interaction::None, MenuButtonAction::SetDifficulty(choice)) => {
+                difficulty_color(*choice, *difficulty)
+            }
+            (Interaction::None, _) => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+fn highlight_difficulty(
+    difficulty: Res<Difficulty>,
+    mut button_query: Query<(&Interaction, &mut BackgroundColor, &MenuButtonAction)>,
+) {
+    if !difficulty.is_changed() {
+        return;
+    }
+    for (interaction, mut color, menu_button_action) in &mut button_query {
+        if let (Interaction::None, MenuButtonAction::SetDifficulty(choice)) =
+            (interaction, menu_button_action)
+        {
+            *color = difficulty_color(*choice, *difficulty).into();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut settings_state: ResMut<NextState<SettingsMenu>>,
+    mut difficulty: ResMut<Difficulty>,
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    dir: Res<DataDir>,
+    mut toasts: EventWriter<Toast>,
+    mut imported: EventWriter<ReplayImported>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::SetDifficulty(choice) => {
+                    *difficulty = *choice;
+                }
+                MenuButtonAction::Play => {
+                    start_run(&mut commands, &config, GameMode::Endless, *difficulty);
+                    game_state.set(GameState::Game);
+                }
+                MenuButtonAction::TimeTrial => {
+                    start_run(&mut commands, &config, GameMode::TimeTrial, *difficulty);
+                    game_state.set(GameState::Game);
+                }
+                MenuButtonAction::Timed => {
+                    start_run(&mut commands, &config, GameMode::Timed, *difficulty);
+                    game_state.set(GameState::Game);
+                }
+                MenuButtonAction::Weekly => {
+                    start_run(&mut commands, &config, GameMode::Weekly, *difficulty);
+                    game_state.set(GameState::Game);
+                }
+                MenuButtonAction::Settings => {
+                    settings_state.set(SettingsMenu::Open);
+                }
+                MenuButtonAction::Stats => {
+                    game_state.set(GameState::Stats);
+                }
+                MenuButtonAction::WatchReplay | MenuButtonAction::WatchImported(_) => {
+                    let replay = match menu_button_action {
+                        MenuButtonAction::WatchImported(index) => {
+                            load_imported_replays(&dir).into_iter().nth(*index)
+                        }
+                        _ => load_replay(&dir),
+                    };
+                    let Some(replay) = replay else {
+                        continue;
+                    };
+                    if replay.altered {
+                        toasts.send(
+                            Toast::new("This run was changed partway and can't be played back")
+                                .with_color(FAILED_COLOR),
+                        );
+                        continue;
+                    }
+                    start_playback(&mut commands, replay);
+                    game_state.set(GameState::Game);
+                }
+                MenuButtonAction::ExportReplay => {
+                    let Some(replay) = load_replay(&dir) else {
+                        continue;
+                    };
+                    match export_replay(&replay) {
+                        Ok(path) => {
+                            info!("Exported the replay to {}", path.display());
+                            toasts.send(Toast::new("Exported replay"));
+                        }
+                        Err(ReplayFileError::Cancelled) => {}
+                        Err(err) => {
+                            warn!("Failed to export the replay: {err}");
+                            toasts.send(
+                                Toast::new(format!("Export failed: {err}"))
+                                    .with_color(FAILED_COLOR),
+                            );
+                        }
+                    }
+                }
+                MenuButtonAction::ImportReplay => match import_replay() {
+                    Ok(replay) => match store_imported_replay(&dir, replay) {
+                        Ok(()) => {
+                            toasts.send(Toast::new("Imported replay"));
+                            imported.send(ReplayImported);
+                        }
+                        Err(err) => {
+                            warn!("Failed to store the imported replay: {err}");
+                            toasts.send(
+                                Toast::new(format!("Import failed: {err}"))
+                                    .with_color(FAILED_COLOR),
+                            );
+                        }
+                    },
+                    Err(ReplayFileError::Cancelled) => {}
+                    Err(err) => {
+                        warn!("Failed to import a replay: {err}");
+                        toasts.send(
+                            Toast::new(format!("Import failed: {err}")).with_color(FAILED_COLOR),
+                        );
+                    }
+                },
+                MenuButtonAction::Customize => {
+                    game_state.set(GameState::Customize);
+                }
+                MenuButtonAction::Quit => {
+                    app_exit_events.send(AppExit::Success);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use bevy::{a11y::Focus, prelude::*, ui::UiSystem};
+
+use crate::{actions::ReadActions, ActionState, HOVERED_BUTTON, PRESSED_BUTTON};
+
+#[derive(Component)]
+pub struct FocusedButton;
+
+// The button the gamepad pressed last frame, released again before the
+// next navigation so it can be pressed repeatedly.
+#[derive(Resource, Default)]
+pub struct GamepadPress(Option<Entity>);
+
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct NavigateMenus;
+
+pub fn menu_focus_plugin(app: &mut App) {
+    app.init_resource::<GamepadPress>()
+        .configure_sets(
+            PreUpdate,
+            NavigateMenus.after(UiSystem::Focus).after(ReadActions),
+        )
+        .add_systems(PostUpdate, tint_focused);
+}
+
+// Buttons are visited top to bottom, then left to right, with `T` picking
+// out the menu's own buttons.
+#[allow(clippy::type_complexity)]
+pub fn navigate<T: Component>(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut press: ResMut<GamepadPress>,
+    mut focus: ResMut<Focus>,
+    mut button_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &mut Interaction,
+            Has<FocusedButton>,
+        ),
+        (With<Button>, With<T>),
+    >,
+) {
+    if let Some(entity) = press.0.take() {
+        if let Ok((_, _, mut interaction, _)) = button_query.get_mut(entity) {
+            if *interaction == Interaction::Pressed {
+                *interaction = Interaction::None;
+            }
+        }
+    }
+
+    let mut buttons: Vec<(Entity, Vec3, bool)> = button_query
+        .iter()
+        .map(|(entity, transform, _, focused)| (entity, transform.translation(), focused))
+        .collect();
+    if buttons.is_empty() {
+        return;
+    }
+    buttons.sort_by(|a, b| a.1.y.total_cmp(&b.1.y).then(a.1.x.total_cmp(&b.1.x)));
+    let current = buttons.iter().position(|(_, _, focused)| *focused);
+
+    let step = match (actions.menu_up, actions.menu_down) {
+        (true, false) => -1,
+        (false, true) => 1,
+        _ => 0,
+    };
+    if step != 0 {
+        let next = match current {
+            Some(index) => (index as i32 + step).rem_euclid(buttons.len() as i32) as usize,
+            None => 0,
+        };
+        if let Some(index) = current {
+            let (entity, ..) = buttons[index];
+            commands.entity(entity).remove::<FocusedButton>();
+            // Lets the menu's own button system restore the normal colour.
+            if let Ok((_, _, mut interaction, _)) = button_query.get_mut(entity) {
+                interaction.set_changed();
+            }
+        }
+        commands.entity(buttons[next].0).insert(FocusedButton);
+        // Screen readers follow the same focus.
+        focus.0 = Some(buttons[next].0);
+    } else if actions.confirm {
+        if let Some(index) = current {
+            let entity = buttons[index].0;
+            if let Ok((_, _, mut interaction, _)) = button_query.get_mut(entity) {
+                *interaction = Interaction::Pressed;
+                press.0 = Some(entity);
+            }
+        }
+    }
+}
+
+// Runs after every menu's button system so the focus tint always wins.
+fn tint_focused(
+    mut button_query: Query<(&Interaction, &mut BackgroundColor), With<FocusedButton>>,
+) {
+    for (interaction, mut color) in &mut button_query {
+        let tint = match interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            _ => HOVERED_BUTTON,
+        };
+        if color.0 != tint {
+            color.0 = tint;
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::*,
+    time::common_conditions::on_timer,
+    window::{MonitorSelection, PresentMode, PrimaryWindow, WindowMode, WindowPosition},
+    winit::WinitWindows,
+};
+
+use crate::{
+    persistence::{store_display_settings, DataDir},
+    DisplaySettings,
+};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    pub size: UVec2,
+}
+
+#[derive(Resource, Default)]
+pub struct AvailableMonitors(pub Vec<MonitorInfo>);
+
+pub fn monitors_plugin(app: &mut App) {
+    app.init_resource::<AvailableMonitors>().add_systems(
+        Update,
+        (
+            refresh_monitors.run_if(on_timer(REFRESH_INTERVAL)),
+            move_to_selected_monitor,
+            toggle_fullscreen,
+            apply_display_settings,
+        )
+            .chain(),
+    );
+}
+
+// There are no winit windows when running headless.
+fn refresh_monitors(
+    winit_windows: Option<NonSend<WinitWindows>>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    mut monitors: ResMut<AvailableMonitors>,
+    mut display_settings: ResMut<DisplaySettings>,
+) {
+    let Some(winit_window) = windows
+        .get_single()
+        .ok()
+        .zip(winit_windows.as_deref())
+        .and_then(|(entity, winit_windows)| winit_windows.get_window(entity))
+    else {
+        return;
+    };
+    let available: Vec<_> = winit_window
+        .available_monitors()
+        .map(|monitor| MonitorInfo {
+            name: monitor.name(),
+            size: UVec2::new(monitor.size().width, monitor.size().height),
+        })
+        .collect();
+
+    let changed = available.len() != monitors.0.len()
+        || available
+            .iter()
+            .zip(&monitors.0)
+            .any(|(a, b)| a.name != b.name || a.size != b.size);
+    if changed {
+        monitors.0 = available;
+    }
+    // The selected monitor was unplugged, so go back to the primary one.
+    if display_settings
+        .monitor
+        .is_some_and(|index| index >= monitors.0.len())
+    {
+        warn!("Selected monitor is no longer available, falling back to the primary monitor");
+        display_settings.monitor = None;
+    }
+}
+
+// The window opens on the primary monitor. A saved monitor is only moved to
+// once the monitors are listed, by which point one that is gone has been reset.
+fn move_to_selected_monitor(
+    display_settings: Res<DisplaySettings>,
+    monitors: Res<AvailableMonitors>,
+    mut placed_on: Local<Option<usize>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if monitors.0.is_empty() || display_settings.monitor == *placed_on {
+        return;
+    }
+    let mut window = match windows.get_single_mut() {
+        Ok(win) => win,
+        Err(_) => return,
+    };
+    *placed_on = display_settings.monitor;
+    // Fullscreen modes follow the monitor the window is on, so centering it is enough.
+    window.position = WindowPosition::Centered(match display_settings.monitor {
+        Some(index) => MonitorSelection::Index(index),
+        None => MonitorSelection::Primary,
+    });
+}
+
+// Goes through the saved setting, so the choice sticks like the one in the menu.
+fn toggle_fullscreen(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut display_settings: ResMut<DisplaySettings>,
+) {
+    if keyboard_input.just_pressed(KeyCode::F11) {
+        display_settings.fullscreen = !display_settings.fullscreen;
+    }
+}
+
+// Also runs once on startup, so the saved choice replaces the defaults in `main`.
+fn apply_display_settings(
+    dir: Res<DataDir>,
+    display_settings: Res<DisplaySettings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !display_settings.is_changed() {
+        return;
+    }
+    let mut window = match windows.get_single_mut() {
+        Ok(win) => win,
+        Err(_) => return,
+    };
+    window.present_mode = if display_settings.vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    };
+    window.mode = if display_settings.fullscreen {
+        WindowMode::BorderlessFullscreen
+    } else {
+        WindowMode::Windowed
+    };
+    if !display_settings.is_added() {
+        store_display_settings(&dir, &display_settings);
+    }
+}
@@ -0,0 +1,285 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{audio::Volume, input::InputSystem, prelude::*};
+use rand::seq::SliceRandom;
+
+use crate::{
+    audio::AudioAssets, despawn_screen, toasts::HudCorner, AudioSettings, CosmeticRng, GameState,
+    PauseMode,
+};
+
+const MUSIC_DIR: &str = "music";
+const MUSIC_VOLUME: f32 = 0.4;
+const TRACK_GAP: f32 = 1.;
+const TRACK_NAME_DURATION: f32 = 3.;
+
+#[derive(Component)]
+struct MusicTrack;
+
+#[derive(Component)]
+struct MenuMusic;
+
+#[derive(Component)]
+struct TrackNameText(Timer);
+
+// Browsers keep audio muted until the page is interacted with, so on the
+// web the menu theme waits for the first key press, click or touch.
+#[derive(Resource)]
+struct AudioUnlocked(bool);
+
+// Where gameplay tracks are picked up from. It is scanned at startup, so a
+// different folder has to be inserted before then, as the tests do.
+#[derive(Resource)]
+pub struct MusicDir(pub PathBuf);
+
+impl Default for MusicDir {
+    fn default() -> Self {
+        Self(default_music_dir())
+    }
+}
+
+#[derive(Resource)]
+struct Playlist {
+    tracks: Vec<(String, Handle<AudioSource>)>,
+    order: Vec<usize>,
+    position: usize,
+    gap: Timer,
+}
+
+pub fn music_plugin(app: &mut App) {
+    app.insert_resource(AudioUnlocked(cfg!(not(target_arch = "wasm32"))))
+        .init_resource::<MusicDir>()
+        .add_systems(Startup, load_playlist)
+        .add_systems(
+            PreUpdate,
+            unlock_audio
+                .after(InputSystem)
+                .run_if(|unlocked: Res<AudioUnlocked>| !unlocked.0),
+        )
+        .add_systems(
+            Update,
+            (advance_playlist, fade_track_name)
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        )
+        .add_systems(
+            Update,
+            start_menu_music.run_if(
+                in_state(GameState::MainMenu).and_then(|unlocked: Res<AudioUnlocked>| unlocked.0),
+            ),
+        )
+        .add_systems(OnExit(GameState::MainMenu), despawn_screen::<MenuMusic>)
+        .add_systems(OnEnter(PauseMode::Paused), pause_music)
+        .add_systems(OnExit(PauseMode::Paused), resume_music)
+        .add_systems(OnExit(GameState::Game), stop_music)
+        .add_systems(
+            Update,
+            update_music_volume.run_if(resource_changed::<AudioSettings>),
+        );
+}
+
+fn music_volume(audio_settings: &AudioSettings) -> Volume {
+    Volume::new(MUSIC_VOLUME * audio_settings.music_volume())
+}
+
+fn looping(source: &Handle<AudioSource>, audio_settings: &AudioSettings) -> AudioBundle {
+    AudioBundle {
+        source: source.clone(),
+        settings: PlaybackSettings::LOOP.with_volume(music_volume(audio_settings)),
+    }
+}
+
+fn unlock_audio(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    touches: Res<Touches>,
+    mut unlocked: ResMut<AudioUnlocked>,
+) {
+    if keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || touches.any_just_pressed()
+    {
+        unlocked.0 = true;
+    }
+}
+
+fn start_menu_music(
+    mut commands: Commands,
+    audio_assets: Res<AudioAssets>,
+    audio_settings: Res<AudioSettings>,
+    menu_music_query: Query<(), With<MenuMusic>>,
+) {
+    if !menu_music_query.is_empty() {
+        return;
+    }
+    commands.spawn((
+        looping(&audio_assets.menu_theme, &audio_settings),
+        MenuMusic,
+    ));
+}
+
+// Tracks that are already playing follow the sliders straight away.
+#[allow(clippy::type_complexity)]
+fn update_music_volume(
+    audio_settings: Res<AudioSettings>,
+    sink_query: Query<&AudioSink, Or<(With<MusicTrack>, With<MenuMusic>)>>,
+) {
+    for sink in &sink_query {
+        sink.set_volume(*music_volume(&audio_settings));
+    }
+}
+
+// Scanned from disk rather than listed in code so tracks dropped into the
+// folder are picked up without rebuilding. With no tracks the game is silent.
+#[cfg(not(target_arch = "wasm32"))]
+fn default_music_dir() -> PathBuf {
+    bevy::asset::io::file::FileAssetReader::get_base_path()
+        .join("assets")
+        .join(MUSIC_DIR)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn music_files(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .collect()
+}
+
+// A web server can't be listed, so there is no gameplay music.
+#[cfg(target_arch = "wasm32")]
+fn default_music_dir() -> PathBuf {
+    PathBuf::new()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn music_files(_dir: &Path) -> Vec<PathBuf> {
+    Vec::new()
+}
+
+// Tracks are loaded by their full path, which the file reader resolves
+// whether or not the folder is under `assets`.
+fn load_playlist(mut commands: Commands, asset_server: Res<AssetServer>, music_dir: Res<MusicDir>) {
+    let mut tracks: Vec<_> = music_files(&music_dir.0)
+        .into_iter()
+        .filter(|path| path.extension().is_some_and(|ext| ext == "ogg"))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_str()?.to_string();
+            Some((name, asset_server.load(path)))
+        })
+        .collect();
+    tracks.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut gap = Timer::from_seconds(TRACK_GAP, TimerMode::Once);
+    gap.tick(gap.duration());
+    commands.insert_resource(Playlist {
+        order: Vec::new(),
+        position: 0,
+        tracks,
+        gap,
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn advance_playlist(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut playlist: ResMut<Playlist>,
+    audio_settings: Res<AudioSettings>,
+    track_query: Query<(Entity, Option<&AudioSink>), With<MusicTrack>>,
+    text_query: Query<Entity, With<TrackNameText>>,
+    corner_query: Query<Entity, With<HudCorner>>,
+    mut cosmetic_rng: ResMut<CosmeticRng>,
+) {
+    if playlist.tracks.is_empty() {
+        return;
+    }
+    if let Ok((entity, sink)) = track_query.get_single() {
+        if sink.is_some_and(|sink| sink.empty()) {
+            commands.entity(entity).despawn();
+            playlist.gap.reset();
+        }
+        return;
+    }
+
+    playlist.gap.tick(time.delta());
+    if !playlist.gap.finished() {
+        return;
+    }
+
+    if playlist.position >= playlist.order.len() {
+        let mut order: Vec<usize> = (0..playlist.tracks.len()).collect();
+        order.shuffle(&mut cosmetic_rng.0);
+        playlist.order = order;
+        playlist.position = 0;
+    }
+    let (name, source) = playlist.tracks[playlist.order[playlist.position]].clone();
+    playlist.position += 1;
+
+    commands.spawn((
+        AudioBundle {
+            source,
+            settings: PlaybackSettings::ONCE.with_volume(music_volume(&audio_settings)),
+        },
+        MusicTrack,
+    ));
+
+    for entity in &text_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    let track_name = commands
+        .spawn((
+            TextBundle::from_section(
+                name,
+                TextStyle {
+                    font_size: 24.,
+                    ..default()
+                },
+            ),
+            TrackNameText(Timer::from_seconds(TRACK_NAME_DURATION, TimerMode::Once)),
+        ))
+        .id();
+    if let Ok(corner) = corner_query.get_single() {
+        commands.entity(corner).add_child(track_name);
+    }
+}
+
+fn fade_track_name(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut text_query: Query<(Entity, &mut TrackNameText, &mut Text)>,
+) {
+    for (entity, mut track_name, mut text) in &mut text_query {
+        track_name.0.tick(time.delta());
+        let alpha = (track_name.0.remaining_secs() / 0.5).min(1.);
+        for section in &mut text.sections {
+            section.style.color.set_alpha(alpha);
+        }
+        if track_name.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn pause_music(sink_query: Query<&AudioSink, With<MusicTrack>>) {
+    for sink in &sink_query {
+        sink.pause();
+    }
+}
+
+fn resume_music(sink_query: Query<&AudioSink, With<MusicTrack>>) {
+    for sink in &sink_query {
+        sink.play();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn stop_music(
+    mut commands: Commands,
+    query: Query<Entity, Or<(With<MusicTrack>, With<TrackNameText>)>>,
+) {
+    for entity in &query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,152 @@
+use bevy::{asset::LoadState, prelude::*};
+
+use crate::{game::OnGameScreen, GameState, MotionPreferences, PauseMode, PlayArea};
+
+pub const PARALLAX_DIR: &str = "textures/parallax/";
+// Heights the layer images are drawn at; widths always match the play area.
+const HILLS_HEIGHT: f32 = 260.;
+const GRASS_HEIGHT: f32 = 80.;
+const HILLS_SCROLL_SPEED: f32 = 15.;
+
+#[derive(Resource)]
+struct ParallaxTextures {
+    sky: Handle<Image>,
+    hills: Handle<Image>,
+    grass: Handle<Image>,
+}
+
+#[derive(Component, Clone, Copy)]
+enum Layer {
+    Sky,
+    Hills,
+    Grass,
+}
+
+impl Layer {
+    fn size(self, play_area: &PlayArea) -> Vec2 {
+        match self {
+            Layer::Sky => Vec2::new(play_area.width, play_area.height),
+            Layer::Hills => Vec2::new(play_area.width, HILLS_HEIGHT),
+            Layer::Grass => Vec2::new(play_area.width, GRASS_HEIGHT),
+        }
+    }
+
+    // Hills and grass rest on the bottom edge.
+    fn y(self, play_area: &PlayArea) -> f32 {
+        match self {
+            Layer::Sky => 0.,
+            Layer::Hills => (HILLS_HEIGHT - play_area.height) / 2.,
+            Layer::Grass => (GRASS_HEIGHT - play_area.height) / 2.,
+        }
+    }
+}
+
+#[derive(Component)]
+struct Scrolling {
+    speed: f32,
+    width: f32,
+}
+
+pub fn parallax_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Loading), load_layers)
+        .add_systems(OnEnter(GameState::Game), spawn_layers)
+        .add_systems(
+            Update,
+            (
+                fit_layers.run_if(in_state(GameState::Game).and_then(resource_changed::<PlayArea>)),
+                scroll_layers
+                    .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+            ),
+        );
+}
+
+fn load_layers(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let load = |file: &str| asset_server.load(format!("{PARALLAX_DIR}{file}"));
+    commands.insert_resource(ParallaxTextures {
+        sky: load("sky.png"),
+        hills: load("hills.png"),
+        grass: load("grass.png"),
+    });
+}
+
+// Layers sit between the menu backdrop at -1 and the gameplay sprites at 0.
+// A layer whose file failed to load is left out.
+fn spawn_layers(
+    mut commands: Commands,
+    layers: Res<ParallaxTextures>,
+    asset_server: Res<AssetServer>,
+    play_area: Res<PlayArea>,
+) {
+    let available = |handle: &Handle<Image>| {
+        !matches!(
+            asset_server.get_load_state(handle),
+            Some(LoadState::Failed(_))
+        )
+    };
+    let layer = |texture: Handle<Image>, layer: Layer, x: f32, z: f32| {
+        (
+            SpriteBundle {
+                transform: Transform::from_xyz(x, layer.y(&play_area), z),
+                texture,
+                sprite: Sprite {
+                    custom_size: Some(layer.size(&play_area)),
+                    ..default()
+                },
+                ..default()
+            },
+            layer,
+            OnGameScreen,
+        )
+    };
+
+    if available(&layers.sky) {
+        commands.spawn(layer(layers.sky.clone(), Layer::Sky, 0., -0.9));
+    }
+    if available(&layers.hills) {
+        let width = play_area.width;
+        // Two copies side by side so one always fills the gap the other leaves.
+        for x in [0., width] {
+            commands.spawn((
+                layer(layers.hills.clone(), Layer::Hills, x, -0.8),
+                Scrolling {
+                    speed: HILLS_SCROLL_SPEED,
+                    width,
+                },
+            ));
+        }
+    }
+    if available(&layers.grass) {
+        commands.spawn(layer(layers.grass.clone(), Layer::Grass, 0., -0.7));
+    }
+}
+
+// Scrolling layers keep how far along they are, scaled to the new width.
+fn fit_layers(
+    play_area: Res<PlayArea>,
+    mut query: Query<(&Layer, &mut Sprite, &mut Transform, Option<&mut Scrolling>)>,
+) {
+    for (layer, mut sprite, mut transform, scrolling) in &mut query {
+        sprite.custom_size = Some(layer.size(&play_area));
+        transform.translation.y = layer.y(&play_area);
+        if let Some(mut scrolling) = scrolling {
+            transform.translation.x *= play_area.width / scrolling.width;
+            scrolling.width = play_area.width;
+        }
+    }
+}
+
+fn scroll_layers(
+    time: Res<Time>,
+    motion_preferences: Res<MotionPreferences>,
+    mut query: Query<(&mut Transform, &Scrolling)>,
+) {
+    if motion_preferences.reduce_motion {
+        return;
+    }
+    for (mut transform, scrolling) in &mut query {
+        transform.translation.x -= scrolling.speed * time.delta_seconds();
+        if transform.translation.x <= -scrolling.width {
+            transform.translation.x += 2. * scrolling.width;
+        }
+    }
+}
@@ -0,0 +1,282 @@
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen,
+    game_over::{save_replay, SaveReplayText},
+    menu_focus::{navigate, NavigateMenus},
+    persistence::DataDir,
+    replay::{InputSource, Replay},
+    start_run, ActionState, Difficulty, GameConfig, GameMode, GameState, PauseMode, SettingsMenu,
+    CAN_QUIT, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+#[derive(Component)]
+struct OnPauseMenuScreen;
+
+#[derive(Component)]
+pub enum MenuButtonAction {
+    Resume,
+    Restart,
+    SaveReplay,
+    Settings,
+    MainMenu,
+    Quit,
+}
+
+pub fn pause_menu_plugin(app: &mut App) {
+    app.add_systems(OnEnter(PauseMode::Paused), setup)
+        .add_systems(OnEnter(GameState::Restarting), finish_restart)
+        .add_systems(
+            Update,
+            (button_system, menu_action).run_if(in_state(PauseMode::Paused)),
+        )
+        .add_systems(Update, toggle_pause.run_if(in_state(GameState::Game)))
+        .add_systems(
+            PreUpdate,
+            navigate::<MenuButtonAction>
+                .in_set(NavigateMenus)
+                .run_if(in_state(PauseMode::Paused).and_then(in_state(SettingsMenu::Closed))),
+        )
+        .add_systems(
+            OnExit(PauseMode::Paused),
+            despawn_screen::<OnPauseMenuScreen>,
+        );
+}
+
+fn setup(mut commands: Commands, input_source: Res<InputSource>) {
+    let button_style = Style {
+        width: Val::Px(250.0),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font_size: 40.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnPauseMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Resume,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Resume",
+                                button_text_style.clone(),
+                            ));
+                        });
+
+                    // A replay has nothing new to record and can't be restarted
+                    // as a live run.
+                    if !input_source.is_replay() {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::Restart,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Restart",
+                                    button_text_style.clone(),
+                                ));
+                            });
+
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::SaveReplay,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        "Save Replay",
+                                        button_text_style.clone(),
+                                    ),
+                                    SaveReplayText,
+                                ));
+                            });
+                    }
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Settings,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Settings",
+                                button_text_style.clone(),
+                            ));
+                        });
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::MainMenu,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Main Menu",
+                                button_text_style.clone(),
+                            ));
+                        });
+
+                    if CAN_QUIT {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::Quit,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Quit",
+                                    button_text_style.clone(),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+// The only system that turns the pause action into a state change, so a
+// single press can't be seen once by the game and again by this menu.
+pub fn toggle_pause(
+    actions: Res<ActionState>,
+    pause_mode: Res<State<PauseMode>>,
+    settings_state: Res<State<SettingsMenu>>,
+    mut next_pause_mode: ResMut<NextState<PauseMode>>,
+) {
+    if !actions.pause {
+        return;
+    }
+    match pause_mode.get() {
+        PauseMode::Countdown | PauseMode::Playing => next_pause_mode.set(PauseMode::Paused),
+        // The settings screen handles its own way back first.
+        PauseMode::Paused if *settings_state.get() == SettingsMenu::Closed => {
+            next_pause_mode.set(PauseMode::Countdown);
+        }
+        PauseMode::Paused => {}
+    }
+}
+
+fn finish_restart(mut screen_state: ResMut<NextState<GameState>>) {
+    screen_state.set(GameState::Game);
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut commands: Commands,
+    mut app_exit_events: EventWriter<AppExit>,
+    mut game_state: ResMut<NextState<PauseMode>>,
+    mut screen_state: ResMut<NextState<GameState>>,
+    mut settings_state: ResMut<NextState<SettingsMenu>>,
+    game_mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    config: Res<GameConfig>,
+    recording: Res<Replay>,
+    dir: Res<DataDir>,
+    mut text_query: Query<&mut Text, With<SaveReplayText>>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Resume => {
+                    game_state.set(PauseMode::Countdown);
+                }
+                MenuButtonAction::SaveReplay => {
+                    save_replay(&dir, &recording, &mut text_query);
+                }
+                // Leaving `Game` reuses its usual cleanup and setup instead
+                // of resetting the run in place.
+                MenuButtonAction::Restart => {
+                    start_run(&mut commands, &config, *game_mode, *difficulty);
+                    screen_state.set(GameState::Restarting);
+                }
+                MenuButtonAction::Settings => {
+                    settings_state.set(SettingsMenu::Open);
+                }
+                // The run's resources are replaced by `start_run` when a new
+                // game begins.
+                MenuButtonAction::MainMenu => {
+                    screen_state.set(GameState::MainMenu);
+                }
+                MenuButtonAction::Quit => {
+                    app_exit_events.send(AppExit::Success);
+                }
+            }
+        }
+    }
+}
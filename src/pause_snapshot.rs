@@ -0,0 +1,190 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        texture::ImageSampler,
+        view::RenderLayers,
+    },
+    window::PrimaryWindow,
+};
+
+use crate::{despawn_screen, MainCamera, PauseMode};
+
+// The game view is rendered at a fraction of the window size and stretched
+// back up with linear filtering, which is enough to read as a blur.
+const SNAPSHOT_DOWNSCALE: u32 = 8;
+const SNAPSHOT_TINT: Color = Color::srgb(0.6, 0.6, 0.6);
+const DIM_OVERLAY: Color = Color::srgba(0., 0., 0., 0.5);
+const DIM_FADE_DURATION: f32 = 0.15;
+
+#[derive(Component)]
+struct OnPauseSnapshot;
+
+#[derive(Component)]
+struct DimFade(Timer);
+
+#[derive(Resource)]
+struct PauseSnapshot {
+    captured: bool,
+}
+
+pub fn pause_snapshot_plugin(app: &mut App) {
+    app.add_systems(OnEnter(PauseMode::Paused), take_snapshot)
+        .add_systems(
+            Last,
+            stop_game_camera.run_if(resource_exists::<PauseSnapshot>),
+        )
+        .add_systems(Update, fade_in_overlay.run_if(in_state(PauseMode::Paused)))
+        .add_systems(
+            OnExit(PauseMode::Paused),
+            (restore_game_camera, despawn_screen::<OnPauseSnapshot>),
+        );
+}
+
+fn take_snapshot(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera_query: Query<(&mut Camera, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    let window = match windows.get_single() {
+        Ok(win) => win,
+        Err(_) => return,
+    };
+    let Ok((mut camera, mut projection)) = camera_query.get_single_mut() else {
+        spawn_overlay(&mut commands, None);
+        return;
+    };
+
+    let size = Extent3d {
+        width: (window.physical_width() / SNAPSHOT_DOWNSCALE).max(1),
+        height: (window.physical_height() / SNAPSHOT_DOWNSCALE).max(1),
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("pause_snapshot"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        sampler: ImageSampler::linear(),
+        ..default()
+    };
+    image.resize(size);
+    let handle = images.add(image);
+
+    // While the game camera draws into the snapshot, the UI needs a camera
+    // of its own that renders straight to the window.
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                order: 1,
+                ..default()
+            },
+            ..default()
+        },
+        RenderLayers::layer(1),
+        OnPauseSnapshot,
+    ));
+    camera.target = RenderTarget::Image(handle.clone());
+    projection.scale = window.width() / size.width as f32;
+    commands.insert_resource(PauseSnapshot { captured: false });
+
+    spawn_overlay(&mut commands, Some(handle));
+}
+
+fn spawn_overlay(commands: &mut Commands, snapshot: Option<Handle<Image>>) {
+    let mut overlay = commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            z_index: ZIndex::Global(-1),
+            ..default()
+        },
+        DimFade(Timer::from_seconds(DIM_FADE_DURATION, TimerMode::Once)),
+        OnPauseSnapshot,
+    ));
+    if let Some(snapshot) = snapshot {
+        overlay.with_children(|parent| {
+            parent.spawn(ImageBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                image: UiImage::new(snapshot),
+                ..default()
+            });
+        });
+    }
+}
+
+// The snapshot covers the overlay's own background, so both are faded in
+// from the undimmed game.
+fn fade_in_overlay(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut overlay_query: Query<(
+        Entity,
+        &mut DimFade,
+        &mut BackgroundColor,
+        Option<&Children>,
+    )>,
+    mut image_query: Query<&mut UiImage>,
+) {
+    for (entity, mut fade, mut background, children) in &mut overlay_query {
+        fade.0.tick(time.delta());
+        let progress = fade.0.fraction();
+        background.0 = DIM_OVERLAY.with_alpha(DIM_OVERLAY.alpha() * progress);
+        for &child in children.into_iter().flatten() {
+            if let Ok(mut image) = image_query.get_mut(child) {
+                image.color = Color::WHITE.mix(&SNAPSHOT_TINT, progress);
+            }
+        }
+        if fade.0.finished() {
+            commands.entity(entity).remove::<DimFade>();
+        }
+    }
+}
+
+fn stop_game_camera(
+    mut snapshot: ResMut<PauseSnapshot>,
+    mut camera_query: Query<&mut Camera, With<MainCamera>>,
+) {
+    if !snapshot.captured {
+        snapshot.captured = true;
+        return;
+    }
+    for mut camera in &mut camera_query {
+        if camera.is_active {
+            camera.is_active = false;
+        }
+    }
+}
+
+fn restore_game_camera(
+    mut commands: Commands,
+    mut camera_query: Query<(&mut Camera, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    commands.remove_resource::<PauseSnapshot>();
+    for (mut camera, mut projection) in &mut camera_query {
+        camera.is_active = true;
+        camera.target = RenderTarget::default();
+        projection.scale = 1.;
+    }
+}
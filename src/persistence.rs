@@ -0,0 +1,401 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    path::PathBuf,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{
+    achievements::Achievement,
+    actions::InputBindings,
+    assists::run_is_unassisted,
+    cheats::run_is_clean,
+    cosmetics::{AppleTheme, BasketSkin},
+    grading::Grade,
+    replay::Replay,
+    weekly::WeekId,
+    AudioSettings, DisplaySettings, GameMode, GameState, Scoreboard,
+};
+
+#[cfg(feature = "twitch")]
+use crate::twitch::TwitchSettings;
+
+const PROFILE_FILE: &str = "profile.ron";
+const HIGH_SCORE_FILE: &str = "highscore.ron";
+const CONTROLS_FILE: &str = "controls.ron";
+const DISPLAY_FILE: &str = "display.ron";
+const AUDIO_FILE: &str = "audio.ron";
+const REPLAY_FILE: &str = "replay.ron";
+const IMPORTED_REPLAYS_FILE: &str = "imported_replays.ron";
+#[cfg(feature = "twitch")]
+const TWITCH_FILE: &str = "twitch.ron";
+pub const PRESTIGE_THRESHOLD: u64 = 1000;
+const PRESTIGE_BONUS: f32 = 0.05;
+const MAGNET_RADII: [f32; 3] = [40., 70., 100.];
+const MAGNET_COSTS: [u64; 3] = [150, 300, 500];
+const MAX_IMPORTED_REPLAYS: usize = 3;
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct Profile {
+    pub best_combo: u32,
+    pub lifetime_catches: u64,
+    pub prestige_level: u32,
+    pub achievements: Vec<Achievement>,
+    pub basket_skin: BasketSkin,
+    pub apple_theme: AppleTheme,
+    pub best_grades: HashMap<GameMode, Grade>,
+    pub magnet_tier: usize,
+    pub weekly_best: BTreeMap<WeekId, u32>,
+    // Fastest finished time trial, where lower is better.
+    pub best_time: Option<Duration>,
+}
+
+// The parts of the profile that change how a run plays out. Replays carry
+// their own, so they play back the same on any profile.
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Loadout {
+    pub magnet_tier: usize,
+    pub basket_skin: BasketSkin,
+    pub prestige_level: u32,
+}
+
+impl Loadout {
+    pub fn score_multiplier(&self) -> f32 {
+        1. + PRESTIGE_BONUS * self.prestige_level as f32
+    }
+
+    pub fn magnet_radius(&self) -> Option<f32> {
+        self.magnet_tier
+            .checked_sub(1)
+            .and_then(|index| MAGNET_RADII.get(index).copied())
+    }
+}
+
+impl Profile {
+    pub fn loadout(&self) -> Loadout {
+        Loadout {
+            magnet_tier: self.magnet_tier,
+            basket_skin: self.active_basket_skin(),
+            prestige_level: self.prestige_level,
+        }
+    }
+
+    pub fn can_prestige(&self) -> bool {
+        self.lifetime_catches >= PRESTIGE_THRESHOLD
+    }
+
+    pub fn prestige(&mut self) {
+        self.lifetime_catches = 0;
+        self.prestige_level += 1;
+    }
+
+    pub fn next_magnet_cost(&self) -> Option<u64> {
+        MAGNET_COSTS.get(self.magnet_tier).copied()
+    }
+
+    // Upgrades are paid for with lifetime catches, trading off against prestige.
+    pub fn buy_magnet_tier(&mut self) {
+        if let Some(cost) = self.next_magnet_cost() {
+            if self.lifetime_catches >= cost {
+                self.lifetime_catches -= cost;
+                self.magnet_tier += 1;
+            }
+        }
+    }
+
+    pub fn has_achievement(&self, achievement: Achievement) -> bool {
+        self.achievements.contains(&achievement)
+    }
+
+    pub fn is_unlocked(&self, unlocked_by: Option<Achievement>) -> bool {
+        unlocked_by.is_none_or(|achievement| self.has_achievement(achievement))
+    }
+
+    pub fn active_basket_skin(&self) -> BasketSkin {
+        if self.is_unlocked(self.basket_skin.unlocked_by()) {
+            self.basket_skin
+        } else {
+            BasketSkin::default()
+        }
+    }
+
+    pub fn active_apple_theme(&self) -> AppleTheme {
+        if self.is_unlocked(self.apple_theme.unlocked_by()) {
+            self.apple_theme
+        } else {
+            AppleTheme::default()
+        }
+    }
+}
+
+// Best endless score, kept in its own file so it survives a corrupt profile.
+// The prestige level it was set at is kept alongside, since prestige scales
+// the score.
+#[derive(Resource, Serialize, Deserialize, Default)]
+#[serde(from = "SavedHighScore")]
+pub struct HighScore {
+    pub score: u32,
+    pub prestige_level: u32,
+}
+
+// Files saved before the prestige level was kept hold just the score.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SavedHighScore {
+    Current {
+        #[serde(default)]
+        score: u32,
+        #[serde(default)]
+        prestige_level: u32,
+    },
+    Legacy((u32,)),
+}
+
+impl From<SavedHighScore> for HighScore {
+    fn from(saved: SavedHighScore) -> Self {
+        match saved {
+            SavedHighScore::Current {
+                score,
+                prestige_level,
+            } => Self {
+                score,
+                prestige_level,
+            },
+            SavedHighScore::Legacy((score,)) => Self {
+                score,
+                prestige_level: 0,
+            },
+        }
+    }
+}
+
+// Where saves are kept. It is read once when the plugin is added, so a
+// different directory has to be inserted before that, as the tests do.
+#[derive(Resource, Clone)]
+pub struct DataDir(pub PathBuf);
+
+impl Default for DataDir {
+    fn default() -> Self {
+        Self(storage::default_dir())
+    }
+}
+
+pub fn persistence_plugin(app: &mut App) {
+    let dir = app
+        .world_mut()
+        .get_resource_or_insert_with(DataDir::default)
+        .clone();
+    app.insert_resource(load::<Profile>(&dir, PROFILE_FILE))
+        .insert_resource(load::<HighScore>(&dir, HIGH_SCORE_FILE))
+        .insert_resource(load::<InputBindings>(&dir, CONTROLS_FILE).with_defaults())
+        .insert_resource(load::<DisplaySettings>(&dir, DISPLAY_FILE))
+        .insert_resource(load::<AudioSettings>(&dir, AUDIO_FILE));
+    #[cfg(feature = "twitch")]
+    app.insert_resource(load::<TwitchSettings>(&dir, TWITCH_FILE));
+    app.add_systems(
+        OnExit(GameState::Game),
+        (
+            save_profile,
+            record_high_score.run_if(run_is_clean.and_then(run_is_unassisted)),
+        ),
+    )
+    .add_systems(
+        Last,
+        (
+            save_profile,
+            record_high_score.run_if(
+                in_state(GameState::Game)
+                    .and_then(run_is_clean)
+                    .and_then(run_is_unassisted),
+            ),
+        )
+            .run_if(on_event::<AppExit>()),
+    );
+}
+
+// Saves are files next to the executable, or local storage entries named
+// after the same files in a browser.
+#[cfg(not(target_arch = "wasm32"))]
+mod storage {
+    use std::{
+        fs, io,
+        path::{Path, PathBuf},
+    };
+
+    pub fn default_dir() -> PathBuf {
+        std::env::current_exe()
+            .ok()
+            .and_then(|path| path.parent().map(Path::to_path_buf))
+            .unwrap_or_default()
+    }
+
+    pub fn describe(dir: &Path, file: &str) -> String {
+        dir.join(file).display().to_string()
+    }
+
+    pub fn exists(dir: &Path, file: &str) -> bool {
+        dir.join(file).is_file()
+    }
+
+    pub fn read(dir: &Path, file: &str) -> io::Result<Option<String>> {
+        match fs::read_to_string(dir.join(file)) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn write(dir: &Path, file: &str, contents: &str) -> io::Result<()> {
+        let path = dir.join(file);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, contents)?;
+        fs::rename(temp_path, path)
+    }
+}
+
+// Local storage is shared by the whole origin, so the directory is ignored.
+#[cfg(target_arch = "wasm32")]
+mod storage {
+    use std::{
+        io,
+        path::{Path, PathBuf},
+    };
+
+    use web_sys::Storage;
+
+    const KEY_PREFIX: &str = "applecatcher/";
+
+    fn local_storage() -> io::Result<Storage> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| io::Error::other("local storage is not available"))
+    }
+
+    pub fn default_dir() -> PathBuf {
+        PathBuf::new()
+    }
+
+    pub fn describe(_dir: &Path, file: &str) -> String {
+        format!("{KEY_PREFIX}{file}")
+    }
+
+    pub fn exists(dir: &Path, file: &str) -> bool {
+        matches!(read(dir, file), Ok(Some(_)))
+    }
+
+    pub fn read(dir: &Path, file: &str) -> io::Result<Option<String>> {
+        local_storage()?
+            .get_item(&describe(dir, file))
+            .map_err(|err| io::Error::other(format!("{err:?}")))
+    }
+
+    pub fn write(dir: &Path, file: &str, contents: &str) -> io::Result<()> {
+        local_storage()?
+            .set_item(&describe(dir, file), contents)
+            .map_err(|err| io::Error::other(format!("{err:?}")))
+    }
+}
+
+fn load<T: DeserializeOwned + Default>(dir: &DataDir, file: &str) -> T {
+    match storage::read(&dir.0, file) {
+        Ok(Some(contents)) => ron::from_str(&contents).unwrap_or_else(|err| {
+            warn!(
+                "Ignoring unreadable {}: {err}",
+                storage::describe(&dir.0, file)
+            );
+            T::default()
+        }),
+        Ok(None) => T::default(),
+        Err(err) => {
+            warn!("Failed to read {}: {err}", storage::describe(&dir.0, file));
+            T::default()
+        }
+    }
+}
+
+fn store<T: Serialize>(dir: &DataDir, file: &str, value: &T) {
+    let result = ron::ser::to_string_pretty(value, default())
+        .map_err(io::Error::other)
+        .and_then(|contents| storage::write(&dir.0, file, &contents));
+    if let Err(err) = result {
+        warn!("Failed to save {}: {err}", storage::describe(&dir.0, file));
+    }
+}
+
+fn save_profile(dir: Res<DataDir>, profile: Res<Profile>) {
+    store_profile(&dir, &profile);
+}
+
+pub fn store_profile(dir: &DataDir, profile: &Profile) {
+    store(dir, PROFILE_FILE, profile);
+}
+
+pub fn store_bindings(dir: &DataDir, bindings: &InputBindings) {
+    store(dir, CONTROLS_FILE, bindings);
+}
+
+pub fn store_display_settings(dir: &DataDir, display_settings: &DisplaySettings) {
+    store(dir, DISPLAY_FILE, display_settings);
+}
+
+pub fn store_audio_settings(dir: &DataDir, audio_settings: &AudioSettings) {
+    store(dir, AUDIO_FILE, audio_settings);
+}
+
+// Replays hold an entry per input change, so they skip the pretty printing.
+pub fn store_replay(dir: &DataDir, replay: &Replay) -> io::Result<()> {
+    let contents = ron::to_string(replay).map_err(io::Error::other)?;
+    storage::write(&dir.0, REPLAY_FILE, &contents)
+}
+
+pub fn has_replay(dir: &DataDir) -> bool {
+    storage::exists(&dir.0, REPLAY_FILE)
+}
+
+// Unlike the settings files there is no default to fall back to.
+pub fn load_replay(dir: &DataDir) -> Option<Replay> {
+    let path = storage::describe(&dir.0, REPLAY_FILE);
+    let contents = storage::read(&dir.0, REPLAY_FILE)
+        .and_then(|contents| contents.ok_or_else(|| io::ErrorKind::NotFound.into()))
+        .map_err(|err| warn!("Failed to read {path}: {err}"))
+        .ok()?;
+    ron::from_str(&contents)
+        .map_err(|err| warn!("Ignoring unreadable {path}: {err}"))
+        .ok()
+}
+
+// Other players' replays are kept apart from the player's own, newest first,
+// so importing one never replaces the last recorded run.
+pub fn load_imported_replays(dir: &DataDir) -> Vec<Replay> {
+    load(dir, IMPORTED_REPLAYS_FILE)
+}
+
+pub fn store_imported_replay(dir: &DataDir, replay: Replay) -> io::Result<()> {
+    let mut replays = load_imported_replays(dir);
+    replays.insert(0, replay);
+    replays.truncate(MAX_IMPORTED_REPLAYS);
+    let contents = ron::to_string(&replays).map_err(io::Error::other)?;
+    storage::write(&dir.0, IMPORTED_REPLAYS_FILE, &contents)
+}
+
+fn record_high_score(
+    dir: Res<DataDir>,
+    game_mode: Res<GameMode>,
+    scoreboard: Res<Scoreboard>,
+    profile: Res<Profile>,
+    mut high_score: ResMut<HighScore>,
+) {
+    let score = scoreboard.score.max(0) as u32;
+    if *game_mode != GameMode::Endless || score <= high_score.score {
+        return;
+    }
+    *high_score = HighScore {
+        score,
+        prestige_level: profile.prestige_level,
+    };
+    store(&dir, HIGH_SCORE_FILE, &*high_score);
+}
@@ -0,0 +1,370 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::ReadActions,
+    cheats::Cheats,
+    game::Player,
+    persistence::{Loadout, Profile},
+    start_run,
+    weekly::{WeekId, WeeklyRun},
+    ActionState, AssistSettings, ControlScheme, Difficulty, GameConfig, GameMode, GameRng,
+    GameState, MainCamera, MovementStyle, PauseMode, Scoreboard, SpawnQueue, SpawnRequest,
+};
+
+// A playback whose score differs from the recording at one of these
+// checkpoints has drifted and is stopped.
+const CHECKPOINT_INTERVAL: u32 = 60;
+
+#[derive(SystemSet, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct ReadTickInput;
+
+// Everything the run reacts to during one fixed tick, whether it came from
+// the player or from a replay.
+#[derive(Resource, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct TickInput {
+    pub left: bool,
+    pub right: bool,
+    // World x of the finger or cursor the basket is following.
+    pub target_x: Option<f32>,
+    pub rewind: bool,
+    // Missing from replays saved before the dash existed.
+    #[serde(default)]
+    pub dash: bool,
+    #[serde(default)]
+    pub defuse: bool,
+}
+
+// A chat drop due this tick. Chat arrives at any time, so requests are taken
+// from the queue a tick at a time and recorded with the inputs.
+#[derive(Resource, Default)]
+pub struct TickSpawn(pub Option<SpawnRequest>);
+
+// Settings the simulation reads that aren't part of the profile. Playback
+// swaps them in for the player's own and puts those back when it stops.
+#[derive(Clone, Default, Serialize, Deserialize)]
+struct RunSettings {
+    cheats: Cheats,
+    assists: AssistSettings,
+    config: GameConfig,
+}
+
+impl RunSettings {
+    fn swap(world: &mut World, settings: RunSettings) -> RunSettings {
+        RunSettings {
+            cheats: std::mem::replace(&mut *world.resource_mut::<Cheats>(), settings.cheats),
+            assists: std::mem::replace(
+                &mut *world.resource_mut::<AssistSettings>(),
+                settings.assists,
+            ),
+            config: std::mem::replace(&mut *world.resource_mut::<GameConfig>(), settings.config),
+        }
+    }
+}
+
+// Inputs are only stored on the tick they change, so an idle basket costs
+// nothing.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+pub struct Replay {
+    seed: u64,
+    game_mode: GameMode,
+    difficulty: Difficulty,
+    #[serde(default)]
+    movement_style: MovementStyle,
+    // The week a weekly run was played in, for its mutators.
+    #[serde(default)]
+    week: Option<WeekId>,
+    // Loaded from an exported file rather than recorded here.
+    #[serde(default)]
+    pub imported: bool,
+    #[serde(default)]
+    loadout: Loadout,
+    #[serde(default)]
+    settings: RunSettings,
+    // The run was changed partway by a config reload or the dev console,
+    // which playback can't reproduce.
+    #[serde(default)]
+    pub altered: bool,
+    ticks: u32,
+    inputs: Vec<(u32, TickInput)>,
+    #[serde(default)]
+    spawns: Vec<(u32, SpawnRequest)>,
+    checkpoints: Vec<(u32, i32)>,
+}
+
+impl Replay {
+    fn record(&mut self, input: TickInput, spawn: Option<&SpawnRequest>, score: i32) {
+        if self.ticks.is_multiple_of(CHECKPOINT_INTERVAL) {
+            self.checkpoints.push((self.ticks, score));
+        }
+        let last = self.inputs.last().map_or_else(default, |&(_, input)| input);
+        if input != last {
+            self.inputs.push((self.ticks, input));
+        }
+        if let Some(spawn) = spawn {
+            self.spawns.push((self.ticks, spawn.clone()));
+        }
+        self.ticks += 1;
+    }
+}
+
+pub struct ReplayCursor {
+    replay: Replay,
+    // The player's own settings, put back once playback stops.
+    live_settings: RunSettings,
+    tick: u32,
+    next_input: usize,
+    next_spawn: usize,
+    next_checkpoint: usize,
+}
+
+#[derive(Resource, Default)]
+pub enum InputSource {
+    #[default]
+    Live,
+    // Boxed, as the cursor carries a whole replay and the player's settings.
+    Replay(Box<ReplayCursor>),
+}
+
+impl InputSource {
+    pub fn is_replay(&self) -> bool {
+        matches!(self, Self::Replay(_))
+    }
+
+    // A replay moves the basket the way it was recorded, whatever the
+    // current setting.
+    pub fn movement_style(&self, setting: MovementStyle) -> MovementStyle {
+        match self {
+            Self::Live => setting,
+            Self::Replay(cursor) => cursor.replay.movement_style,
+        }
+    }
+
+    // Likewise for the upgrades, skin and prestige of the profile that
+    // recorded it.
+    pub fn loadout(&self, profile: &Profile) -> Loadout {
+        match self {
+            Self::Live => profile.loadout(),
+            Self::Replay(cursor) => cursor.replay.loadout,
+        }
+    }
+
+    // Playback keeps the config it was recorded with, so a reload meanwhile
+    // is held back until it stops.
+    pub fn reload_config(&mut self, config: &mut GameConfig, loaded: GameConfig) {
+        match self {
+            Self::Live => *config = loaded,
+            Self::Replay(cursor) => cursor.live_settings.config = loaded,
+        }
+    }
+}
+
+// Rewind, dash and defuse presses only last a frame, which may not run a fixed
+// tick, so they are held until the next one does.
+#[derive(Resource, Default)]
+struct HeldActions {
+    rewind: bool,
+    dash: bool,
+    defuse: bool,
+}
+
+pub fn replay_plugin(app: &mut App) {
+    app.init_resource::<Replay>()
+        .init_resource::<InputSource>()
+        .init_resource::<TickInput>()
+        .init_resource::<TickSpawn>()
+        .init_resource::<HeldActions>()
+        .add_systems(OnEnter(GameState::Game), start_recording)
+        .add_systems(OnEnter(GameState::GameOver), leave_playback)
+        .add_systems(OnEnter(GameState::Results), leave_playback)
+        .add_systems(OnEnter(GameState::MainMenu), stop_playback)
+        .add_systems(
+            PreUpdate,
+            hold_actions
+                .after(ReadActions)
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        )
+        .add_systems(
+            FixedUpdate,
+            (live_input, playback_input)
+                .chain()
+                .in_set(ReadTickInput)
+                // Ticks are only counted while the game itself advances.
+                .run_if(
+                    in_state(GameState::Game)
+                        .and_then(in_state(PauseMode::Playing))
+                        .and_then(any_with_component::<Player>),
+                ),
+        );
+}
+
+pub fn start_playback(commands: &mut Commands, replay: Replay) {
+    start_run(
+        commands,
+        &replay.settings.config,
+        replay.game_mode,
+        replay.difficulty,
+    );
+    commands.insert_resource(GameRng::from_seed(replay.seed));
+    if let Some(week) = replay.week {
+        commands.insert_resource(WeeklyRun(week));
+    }
+    commands.add(move |world: &mut World| {
+        let live_settings = RunSettings::swap(world, replay.settings.clone());
+        world.insert_resource(InputSource::Replay(Box::new(ReplayCursor {
+            replay,
+            live_settings,
+            tick: 0,
+            next_input: 0,
+            next_spawn: 0,
+            next_checkpoint: 0,
+        })));
+    });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn start_recording(
+    input_source: Res<InputSource>,
+    game_rng: Res<GameRng>,
+    game_mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    movement_style: Res<MovementStyle>,
+    weekly_run: Option<Res<WeeklyRun>>,
+    profile: Res<Profile>,
+    cheats: Res<Cheats>,
+    assist_settings: Res<AssistSettings>,
+    config: Res<GameConfig>,
+    mut recording: ResMut<Replay>,
+    mut tick_input: ResMut<TickInput>,
+    mut tick_spawn: ResMut<TickSpawn>,
+    mut held_actions: ResMut<HeldActions>,
+) {
+    *tick_input = default();
+    *tick_spawn = default();
+    *held_actions = default();
+    // The last live run stays available to save while a replay is watched.
+    if input_source.is_replay() {
+        return;
+    }
+    *recording = Replay {
+        seed: game_rng.seed,
+        game_mode: *game_mode,
+        difficulty: *difficulty,
+        movement_style: *movement_style,
+        week: weekly_run.map(|weekly_run| weekly_run.0),
+        loadout: profile.loadout(),
+        settings: RunSettings {
+            cheats: cheats.clone(),
+            assists: assist_settings.clone(),
+            config: config.clone(),
+        },
+        ..default()
+    };
+}
+
+// A replay ends on the menu rather than on a results screen of its own.
+fn leave_playback(input_source: Res<InputSource>, mut game_state: ResMut<NextState<GameState>>) {
+    if input_source.is_replay() {
+        game_state.set(GameState::MainMenu);
+    }
+}
+
+fn stop_playback(world: &mut World) {
+    let input_source = std::mem::take(&mut *world.resource_mut::<InputSource>());
+    if let InputSource::Replay(cursor) = input_source {
+        RunSettings::swap(world, cursor.live_settings);
+    }
+}
+
+fn hold_actions(actions: Res<ActionState>, mut held_actions: ResMut<HeldActions>) {
+    held_actions.rewind |= actions.rewind;
+    held_actions.dash |= actions.dash;
+    held_actions.defuse |= actions.defuse;
+}
+
+#[allow(clippy::too_many_arguments)]
+fn live_input(
+    input_source: Res<InputSource>,
+    actions: Res<ActionState>,
+    control_scheme: Res<ControlScheme>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    scoreboard: Res<Scoreboard>,
+    mut spawn_queue: ResMut<SpawnQueue>,
+    mut recording: ResMut<Replay>,
+    mut tick_input: ResMut<TickInput>,
+    mut tick_spawn: ResMut<TickSpawn>,
+    mut held_actions: ResMut<HeldActions>,
+) {
+    if input_source.is_replay() {
+        return;
+    }
+    // In mouse mode the basket simply stops while the cursor is outside the window.
+    let cursor_x = match *control_scheme {
+        ControlScheme::Mouse => windows.get_single().ok().and_then(|window| {
+            let cursor = window.cursor_position()?;
+            let (camera, camera_transform) = camera_query.get_single().ok()?;
+            Some(camera.viewport_to_world_2d(camera_transform, cursor)?.x)
+        }),
+        ControlScheme::Keyboard => None,
+    };
+    let keyboard = *control_scheme == ControlScheme::Keyboard;
+    let input = TickInput {
+        left: keyboard && actions.left,
+        right: keyboard && actions.right,
+        target_x: actions.drag_x.or(cursor_x),
+        rewind: std::mem::take(&mut held_actions.rewind),
+        dash: std::mem::take(&mut held_actions.dash),
+        defuse: std::mem::take(&mut held_actions.defuse),
+    };
+    let spawn = spawn_queue.0.pop_front();
+    recording.record(input, spawn.as_ref(), scoreboard.score);
+    *tick_input = input;
+    tick_spawn.0 = spawn;
+}
+
+fn playback_input(
+    mut input_source: ResMut<InputSource>,
+    scoreboard: Res<Scoreboard>,
+    mut tick_input: ResMut<TickInput>,
+    mut tick_spawn: ResMut<TickSpawn>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let InputSource::Replay(cursor) = &mut *input_source else {
+        return;
+    };
+    let replay = &cursor.replay;
+    if cursor.tick >= replay.ticks {
+        *tick_input = default();
+        game_state.set(GameState::MainMenu);
+        return;
+    }
+    if let Some(&(tick, score)) = replay.checkpoints.get(cursor.next_checkpoint) {
+        if tick == cursor.tick {
+            cursor.next_checkpoint += 1;
+            if score != scoreboard.score {
+                warn!("Replay went out of sync at tick {tick}, stopping playback");
+                // Any further ticks this frame just idle until the menu opens.
+                cursor.tick = replay.ticks;
+                *tick_input = default();
+                game_state.set(GameState::MainMenu);
+                return;
+            }
+        }
+    }
+    while let Some(&(tick, input)) = replay.inputs.get(cursor.next_input) {
+        if tick > cursor.tick {
+            break;
+        }
+        *tick_input = input;
+        cursor.next_input += 1;
+    }
+    tick_spawn.0 = None;
+    if let Some((tick, spawn)) = replay.spawns.get(cursor.next_spawn) {
+        if *tick == cursor.tick {
+            tick_spawn.0 = Some(spawn.clone());
+            cursor.next_spawn += 1;
+        }
+    }
+    cursor.tick += 1;
+}
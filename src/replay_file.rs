@@ -0,0 +1,169 @@
+use std::{fmt, fs, io, path::PathBuf};
+
+use crate::replay::Replay;
+
+const EXTENSION: &str = "acr";
+const MAGIC: &str = "ACR";
+// Bumped whenever `Replay` changes in a way older builds can't play back.
+const FORMAT_VERSION: u32 = 2;
+
+#[derive(Debug)]
+pub enum ReplayFileError {
+    Cancelled,
+    // Only the web build has no file dialogs.
+    #[cfg(target_arch = "wasm32")]
+    Unavailable,
+    Io(io::Error),
+    NotAReplay,
+    UnsupportedVersion(u32),
+    ChecksumMismatch,
+    Corrupt(String),
+}
+
+impl fmt::Display for ReplayFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Cancelled => write!(f, "cancelled"),
+            #[cfg(target_arch = "wasm32")]
+            Self::Unavailable => write!(f, "file dialogs are not available"),
+            Self::Io(err) => write!(f, "{err}"),
+            Self::NotAReplay => write!(f, "not a replay file"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "made by an incompatible version (format {version})")
+            }
+            Self::ChecksumMismatch => write!(f, "the file is damaged"),
+            Self::Corrupt(err) => write!(f, "unreadable replay: {err}"),
+        }
+    }
+}
+
+impl From<io::Error> for ReplayFileError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+// A header line with the format version and a checksum of the rest, which is
+// the replay as RON.
+fn encode(replay: &Replay) -> Result<String, ReplayFileError> {
+    let body = ron::to_string(replay).map_err(|err| ReplayFileError::Corrupt(err.to_string()))?;
+    Ok(format!(
+        "{MAGIC} {FORMAT_VERSION} {:016x}\n{body}",
+        checksum(body.as_bytes())
+    ))
+}
+
+fn decode(contents: &str) -> Result<Replay, ReplayFileError> {
+    let (header, body) = contents
+        .split_once('\n')
+        .ok_or(ReplayFileError::NotAReplay)?;
+    let mut fields = header.split(' ');
+    if fields.next() != Some(MAGIC) {
+        return Err(ReplayFileError::NotAReplay);
+    }
+    let version: u32 = fields
+        .next()
+        .and_then(|version| version.parse().ok())
+        .ok_or(ReplayFileError::NotAReplay)?;
+    if version != FORMAT_VERSION {
+        return Err(ReplayFileError::UnsupportedVersion(version));
+    }
+    let expected = fields
+        .next()
+        .and_then(|checksum| u64::from_str_radix(checksum, 16).ok())
+        .ok_or(ReplayFileError::NotAReplay)?;
+    if checksum(body.as_bytes()) != expected {
+        return Err(ReplayFileError::ChecksumMismatch);
+    }
+    ron::from_str(body).map_err(|err| ReplayFileError::Corrupt(err.to_string()))
+}
+
+// FNV-1a, which is enough to notice a truncated or edited file.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub fn export_replay(replay: &Replay) -> Result<PathBuf, ReplayFileError> {
+    let path = pick_export_path()?;
+    fs::write(&path, encode(replay)?)?;
+    Ok(path)
+}
+
+// Imported replays are marked as such, so they are never taken for the
+// player's own.
+pub fn import_replay() -> Result<Replay, ReplayFileError> {
+    let path = pick_import_path()?;
+    let mut replay = decode(&fs::read_to_string(path)?)?;
+    replay.imported = true;
+    Ok(replay)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn pick_export_path() -> Result<PathBuf, ReplayFileError> {
+    rfd::FileDialog::new()
+        .add_filter("Apple Catcher replay", &[EXTENSION])
+        .set_file_name(format!("replay.{EXTENSION}"))
+        .save_file()
+        .ok_or(ReplayFileError::Cancelled)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn pick_import_path() -> Result<PathBuf, ReplayFileError> {
+    rfd::FileDialog::new()
+        .add_filter("Apple Catcher replay", &[EXTENSION])
+        .pick_file()
+        .ok_or(ReplayFileError::Cancelled)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn pick_export_path() -> Result<PathBuf, ReplayFileError> {
+    Err(ReplayFileError::Unavailable)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn pick_import_path() -> Result<PathBuf, ReplayFileError> {
+    Err(ReplayFileError::Unavailable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_replay_survives_the_round_trip() {
+        let replay = Replay::default();
+        let decoded = decode(&encode(&replay).unwrap()).unwrap();
+        assert_eq!(
+            ron::to_string(&decoded).unwrap(),
+            ron::to_string(&replay).unwrap()
+        );
+    }
+
+    #[test]
+    fn an_edited_file_fails_the_checksum() {
+        let contents = encode(&Replay::default())
+            .unwrap()
+            .replacen("ticks:0", "ticks:9", 1);
+        assert!(matches!(
+            decode(&contents),
+            Err(ReplayFileError::ChecksumMismatch)
+        ));
+    }
+
+    #[test]
+    fn other_versions_and_other_files_are_rejected() {
+        let contents = encode(&Replay::default()).unwrap();
+        let newer = contents.replacen(
+            &format!("{MAGIC} {FORMAT_VERSION}"),
+            &format!("{MAGIC} 99"),
+            1,
+        );
+        assert!(matches!(
+            decode(&newer),
+            Err(ReplayFileError::UnsupportedVersion(99))
+        ));
+        assert!(matches!(decode("hello"), Err(ReplayFileError::NotAReplay)));
+    }
+}
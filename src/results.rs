@@ -0,0 +1,568 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen,
+    grading::grade_run,
+    persistence::Profile,
+    start_run,
+    stats::SCORE_SAMPLE_INTERVAL,
+    time_trial::{format_time, TimeTrial, SPLIT_INTERVAL},
+    Difficulty, GameConfig, GameMode, GameRng, GameState, PlayArea, RunAssists, RunStats,
+    Scoreboard, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+const HEATMAP_BUCKETS: usize = 20;
+const HEATMAP_WIDTH: f32 = 400.;
+const HEATMAP_COLD: Color = Color::srgb(0.1, 0.15, 0.1);
+const HEATMAP_HOT: Color = Color::srgb(0.95, 0.9, 0.2);
+const MISS_TICK: Color = Color::srgb(0.9, 0.15, 0.15);
+const LIFE_LOST_MARKER: Color = Color::srgb(1., 0.55, 0.1);
+const FRENZY_MARKER: Color = Color::srgb(0.35, 0.8, 1.);
+const GRAPH_WIDTH: f32 = 400.;
+const GRAPH_HEIGHT: f32 = 70.;
+const GRAPH_MAX_POINTS: usize = 120;
+const GRAPH_LINE: Color = Color::srgb(0.95, 0.9, 0.2);
+const GRAPH_BACKGROUND: Color = Color::srgb(0.1, 0.1, 0.1);
+const LINE_THICKNESS: f32 = 2.;
+
+#[derive(Component)]
+struct OnResultsScreen;
+
+#[derive(Component)]
+struct SeedText;
+
+#[derive(Component)]
+struct CopySeedText;
+
+#[derive(Component)]
+enum MenuButtonAction {
+    CopySeed,
+    PlayAgain,
+    MainMenu,
+}
+
+pub fn results_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Results), setup)
+        .add_systems(
+            Update,
+            (button_system, menu_action).run_if(in_state(GameState::Results)),
+        )
+        .add_systems(
+            OnExit(GameState::Results),
+            despawn_screen::<OnResultsScreen>,
+        );
+}
+
+#[allow(clippy::too_many_arguments)]
+fn setup(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    scoreboard: Res<Scoreboard>,
+    time_trial: Option<Res<TimeTrial>>,
+    run_assists: Res<RunAssists>,
+    run_stats: Res<RunStats>,
+    profile: Res<Profile>,
+    game_rng: Res<GameRng>,
+    play_area: Res<PlayArea>,
+) {
+    let play_width = play_area.width;
+    let button_style = Style {
+        width: Val::Px(250.0),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font_size: 40.0,
+        ..default()
+    };
+    let line_text_style = TextStyle {
+        font_size: 30.0,
+        ..default()
+    };
+
+    let mut lines = match (*game_mode, time_trial) {
+        (GameMode::TimeTrial, Some(time_trial)) => {
+            let mut lines = vec![format!("Time: {}", format_time(time_trial.elapsed))];
+            if time_trial.new_best {
+                lines.push("New best time!".to_string());
+            } else if let Some(best_time) = profile.best_time {
+                lines.push(format!("Best: {}", format_time(best_time)));
+            }
+            lines.extend(time_trial.splits.iter().enumerate().map(|(index, split)| {
+                format!(
+                    "{} catches: {}",
+                    (index as u32 + 1) * SPLIT_INTERVAL,
+                    format_time(*split)
+                )
+            }));
+            lines
+        }
+        _ => vec![format!("Score: {}", scoreboard.score)],
+    };
+    lines.push(format!("Best combo: {}", run_stats.best_combo));
+    lines.push(breakdown(&run_stats));
+    if let Some(grade) = grade_run(&run_stats) {
+        lines.push(grade.summary());
+    }
+    if run_assists.lowest_game_speed < 100 {
+        lines.push(format!(
+            "Played at {}% game speed",
+            run_assists.lowest_game_speed
+        ));
+    }
+    if run_assists.highest_basket_scale > 100 {
+        lines.push(format!(
+            "Played with a {}% basket",
+            run_assists.highest_basket_scale
+        ));
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnResultsScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Results",
+                        TextStyle {
+                            font_size: 60.,
+                            ..default()
+                        },
+                    ));
+
+                    for line in lines {
+                        parent.spawn(TextBundle::from_section(line, line_text_style.clone()));
+                    }
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::FlexEnd,
+                                column_gap: Val::Px(30.),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            if !run_stats.catch_xs.is_empty() || !run_stats.miss_xs.is_empty() {
+                                spawn_heatmap(parent, &run_stats, play_width);
+                            }
+                            if run_stats.elapsed > 0. {
+                                spawn_score_graph(parent, &run_stats, scoreboard.score);
+                            }
+                        });
+
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                align_items: AlignItems::Center,
+                                margin: UiRect::top(Val::Px(20.)),
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                TextBundle::from_section(
+                                    format!("Seed: {}", game_rng.seed),
+                                    line_text_style.clone(),
+                                ),
+                                SeedText,
+                            ));
+
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            height: Val::Px(40.0),
+                                            margin: UiRect::left(Val::Px(20.0)),
+                                            padding: UiRect::horizontal(Val::Px(12.0)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..default()
+                                        },
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    MenuButtonAction::CopySeed,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        TextBundle::from_section(
+                                            "Copy seed",
+                                            line_text_style.clone(),
+                                        ),
+                                        CopySeedText,
+                                    ));
+                                });
+                        });
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::PlayAgain,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Play Again",
+                                button_text_style.clone(),
+                            ));
+                        });
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style.clone(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::MainMenu,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                "Main Menu",
+                                button_text_style.clone(),
+                            ));
+                        });
+                });
+        });
+}
+
+// Also shown on the game over screen, so every way a run ends reports the same numbers.
+pub fn breakdown(run_stats: &RunStats) -> String {
+    format!(
+        "Caught: {}  Missed: {}  Time: {}",
+        run_stats.catch_xs.len(),
+        run_stats.miss_xs.len(),
+        format_time(Duration::from_secs_f32(run_stats.elapsed))
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn copy_to_clipboard(text: String) -> Result<(), String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn copy_to_clipboard(_text: String) -> Result<(), String> {
+    Err("clipboard access is not available".to_string())
+}
+
+fn play_fraction(x: f32, play_width: f32) -> f32 {
+    ((x + play_width / 2.) / play_width).clamp(0., 1.)
+}
+
+fn heatmap_buckets(xs: &[f32], play_width: f32) -> [u32; HEATMAP_BUCKETS] {
+    let mut buckets = [0; HEATMAP_BUCKETS];
+    for &x in xs {
+        let bucket = (play_fraction(x, play_width) * HEATMAP_BUCKETS as f32) as usize;
+        buckets[bucket.min(HEATMAP_BUCKETS - 1)] += 1;
+    }
+    buckets
+}
+
+fn graph_points(run_stats: &RunStats, final_score: i32) -> Vec<(f32, u32)> {
+    let mut points: Vec<_> = run_stats
+        .score_samples
+        .iter()
+        .enumerate()
+        .map(|(index, &score)| (index as f32 * SCORE_SAMPLE_INTERVAL, score))
+        .collect();
+    points.push((run_stats.elapsed, final_score.max(0) as u32));
+
+    if points.len() <= GRAPH_MAX_POINTS {
+        return points;
+    }
+    let last = points.len() - 1;
+    (0..GRAPH_MAX_POINTS)
+        .map(|index| points[index * last / (GRAPH_MAX_POINTS - 1)])
+        .collect()
+}
+
+fn spawn_score_graph(parent: &mut ChildBuilder, run_stats: &RunStats, final_score: i32) {
+    let points = graph_points(run_stats, final_score);
+    let duration = run_stats.elapsed.max(SCORE_SAMPLE_INTERVAL);
+    let max_score = points
+        .iter()
+        .map(|&(_, score)| score)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let to_graph = |(time, score): (f32, u32)| {
+        Vec2::new(
+            (time / duration).clamp(0., 1.) * GRAPH_WIDTH,
+            score as f32 / max_score as f32 * (GRAPH_HEIGHT - LINE_THICKNESS),
+        )
+    };
+    let label_style = TextStyle {
+        font_size: 16.,
+        ..default()
+    };
+
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::top(Val::Px(20.)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                max_score.to_string(),
+                label_style.clone(),
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(GRAPH_WIDTH),
+                        height: Val::Px(GRAPH_HEIGHT),
+                        ..default()
+                    },
+                    background_color: GRAPH_BACKGROUND.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for &time in &run_stats.miss_times {
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(to_graph((time, 0)).x),
+                                width: Val::Px(1.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            background_color: MISS_TICK.with_alpha(0.6).into(),
+                            ..default()
+                        });
+                    }
+                    // Drawn over the miss ticks, as a lost life is usually a miss too.
+                    for &time in &run_stats.life_lost_times {
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(to_graph((time, 0)).x - 1.),
+                                width: Val::Px(3.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            background_color: LIFE_LOST_MARKER.into(),
+                            ..default()
+                        });
+                    }
+                    for &time in &run_stats.frenzy_times {
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(to_graph((time, 0)).x - 1.),
+                                width: Val::Px(3.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            background_color: FRENZY_MARKER.into(),
+                            ..default()
+                        });
+                    }
+
+                    // Each segment is drawn as a box spanning both endpoints,
+                    // which reads as a stepped line without rotated nodes.
+                    for pair in points.windows(2) {
+                        let start = to_graph(pair[0]);
+                        let end = to_graph(pair[1]);
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Px(start.x),
+                                bottom: Val::Px(start.y.min(end.y)),
+                                width: Val::Px((end.x - start.x).max(LINE_THICKNESS)),
+                                height: Val::Px((end.y - start.y).abs() + LINE_THICKNESS),
+                                ..default()
+                            },
+                            background_color: GRAPH_LINE.into(),
+                            ..default()
+                        });
+                    }
+                });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(GRAPH_WIDTH),
+                        justify_content: JustifyContent::SpaceBetween,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        format_time(Duration::ZERO),
+                        label_style.clone(),
+                    ));
+                    parent.spawn(TextBundle::from_section(
+                        format_time(Duration::from_secs_f32(run_stats.elapsed)),
+                        label_style,
+                    ));
+                });
+        });
+}
+
+fn spawn_heatmap(parent: &mut ChildBuilder, run_stats: &RunStats, play_width: f32) {
+    let buckets = heatmap_buckets(&run_stats.catch_xs, play_width);
+    let densest = buckets.iter().copied().max().unwrap_or(0).max(1);
+
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                margin: UiRect::top(Val::Px(20.)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(HEATMAP_WIDTH),
+                        height: Val::Px(24.),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for count in buckets {
+                        let density = count as f32 / densest as f32;
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Percent(100. / HEATMAP_BUCKETS as f32),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            background_color: HEATMAP_COLD.mix(&HEATMAP_HOT, density).into(),
+                            ..default()
+                        });
+                    }
+                });
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        width: Val::Px(HEATMAP_WIDTH),
+                        height: Val::Px(10.),
+                        margin: UiRect::top(Val::Px(2.)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for &x in &run_stats.miss_xs {
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                left: Val::Percent(play_fraction(x, play_width) * 100.),
+                                width: Val::Px(2.),
+                                height: Val::Percent(100.),
+                                ..default()
+                            },
+                            background_color: MISS_TICK.into(),
+                            ..default()
+                        });
+                    }
+                });
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    difficulty: Res<Difficulty>,
+    game_rng: Res<GameRng>,
+    mut seed_text_query: Query<&mut Text, (With<SeedText>, Without<CopySeedText>)>,
+    mut copy_text_query: Query<&mut Text, With<CopySeedText>>,
+    config: Res<GameConfig>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::CopySeed => {
+                    let label = match copy_to_clipboard(game_rng.seed.to_string()) {
+                        Ok(()) => "Copied!",
+                        Err(err) => {
+                            warn!("Failed to copy seed to the clipboard: {err}");
+                            for mut text in &mut seed_text_query {
+                                text.sections[0].style.color = Color::srgb(1., 0.85, 0.2);
+                            }
+                            "Copy it manually"
+                        }
+                    };
+                    for mut text in &mut copy_text_query {
+                        text.sections[0].value = label.to_string();
+                    }
+                }
+                MenuButtonAction::PlayAgain => {
+                    start_run(&mut commands, &config, *game_mode, *difficulty);
+                    game_state.set(GameState::Game);
+                }
+                MenuButtonAction::MainMenu => {
+                    game_state.set(GameState::MainMenu);
+                }
+            }
+        }
+    }
+}
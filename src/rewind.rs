@@ -0,0 +1,298 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::prelude::*;
+
+use crate::{
+    birds::{spawn_bird, Bird},
+    despawn_screen,
+    game::{
+        spawn_apple, spawn_bomb, AppleKind, Falling, GameTick, Hanging, Player, Velocity, Weight,
+    },
+    interpolation::PreviousTransform,
+    lit_bomb::LitBomb,
+    persistence::Profile,
+    replay::{ReadTickInput, TickInput},
+    textures::Textures,
+    AppleSpawnerConfig, GameConfig, GameRng, GameState, Lives, PauseMode, PowerUp,
+    PowerUpCollectedEvent, RunStats, Scoreboard,
+};
+
+const REWIND_TICKS: usize = 180;
+const MAX_CHARGES: u32 = 3;
+
+// An apple, or a bomb when `apple` is empty. Velocities are only assigned on
+// the tick after an apple appears, and not at all while it hangs.
+struct FallingSnapshot {
+    translation: Vec3,
+    apple: Option<(AppleKind, Option<PowerUp>)>,
+    motion: Option<(Vec2, f32)>,
+    hanging: Option<Timer>,
+}
+
+// The run's stats are stored as counts, since rewinding only ever drops the
+// entries added since.
+struct StatsSnapshot {
+    catches: usize,
+    misses: usize,
+    life_lost: usize,
+    frenzies: usize,
+    best_combo: u32,
+    new_combo_record: bool,
+    hazards_avoided: u32,
+    lifetime_catches: u64,
+    profile_best_combo: u32,
+    achievements: usize,
+}
+
+struct WorldSnapshot {
+    falling: Vec<FallingSnapshot>,
+    birds: Vec<(Vec3, Vec2)>,
+    basket: Vec3,
+    lit_bomb: Option<LitBomb>,
+    score: i32,
+    combo: u32,
+    fractional_points: f32,
+    lives: u32,
+    spawn_elapsed: Duration,
+    stats: StatsSnapshot,
+}
+
+#[derive(Resource, Default)]
+struct RewindHistory {
+    snapshots: VecDeque<WorldSnapshot>,
+    charges: u32,
+}
+
+#[derive(Component)]
+struct RewindText;
+
+pub fn rewind_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Game), setup)
+        .add_systems(
+            FixedPostUpdate,
+            (collect_charges, record_snapshot)
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        )
+        // Taken from the tick's input so replays rewind on the same tick.
+        .add_systems(
+            FixedUpdate,
+            activate_rewind
+                .after(ReadTickInput)
+                .before(GameTick)
+                .run_if(
+                    in_state(GameState::Game)
+                        .and_then(in_state(PauseMode::Playing))
+                        .and_then(any_with_component::<Player>),
+                ),
+        )
+        .add_systems(
+            Update,
+            update_rewind_text
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        )
+        .add_systems(OnExit(GameState::Game), despawn_screen::<RewindText>);
+}
+
+fn setup(mut commands: Commands) {
+    commands.insert_resource(RewindHistory::default());
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 24.,
+                color: PowerUp::Rewind.tint(),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.),
+            right: Val::Px(10.),
+            ..default()
+        }),
+        RewindText,
+    ));
+}
+
+fn collect_charges(
+    mut power_up_events: EventReader<PowerUpCollectedEvent>,
+    mut history: ResMut<RewindHistory>,
+) {
+    for PowerUpCollectedEvent(power_up) in power_up_events.read() {
+        if *power_up == PowerUp::Rewind {
+            history.charges = (history.charges + 1).min(MAX_CHARGES);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn record_snapshot(
+    mut history: ResMut<RewindHistory>,
+    falling_query: Query<
+        (
+            &Transform,
+            Option<&AppleKind>,
+            Option<&PowerUp>,
+            Option<(&Velocity, &Weight)>,
+            Option<&Hanging>,
+        ),
+        With<Falling>,
+    >,
+    bird_query: Query<(&Transform, &Velocity), With<Bird>>,
+    player_query: Query<(&Transform, Option<&LitBomb>), With<Player>>,
+    scoreboard: Res<Scoreboard>,
+    lives: Res<Lives>,
+    spawner: Res<AppleSpawnerConfig>,
+    run_stats: Res<RunStats>,
+    profile: Res<Profile>,
+) {
+    let Ok((basket, lit_bomb)) = player_query.get_single() else {
+        return;
+    };
+    let snapshot = WorldSnapshot {
+        falling: falling_query
+            .iter()
+            .map(
+                |(transform, kind, power_up, motion, hanging)| FallingSnapshot {
+                    translation: transform.translation,
+                    apple: kind.map(|&kind| (kind, power_up.copied())),
+                    motion: motion.map(|(velocity, weight)| (velocity.0, weight.0)),
+                    hanging: hanging.map(|hanging| hanging.0.clone()),
+                },
+            )
+            .collect(),
+        birds: bird_query
+            .iter()
+            .map(|(transform, velocity)| (transform.translation, velocity.0))
+            .collect(),
+        basket: basket.translation,
+        lit_bomb: lit_bomb.cloned(),
+        score: scoreboard.score,
+        combo: scoreboard.combo,
+        fractional_points: scoreboard.fractional_points,
+        lives: lives.0,
+        spawn_elapsed: spawner.timer.elapsed(),
+        stats: StatsSnapshot {
+            catches: run_stats.catch_xs.len(),
+            misses: run_stats.miss_xs.len(),
+            life_lost: run_stats.life_lost_times.len(),
+            frenzies: run_stats.frenzy_times.len(),
+            best_combo: run_stats.best_combo,
+            new_combo_record: run_stats.new_combo_record,
+            hazards_avoided: run_stats.hazards_avoided,
+            lifetime_catches: profile.lifetime_catches,
+            profile_best_combo: profile.best_combo,
+            achievements: profile.achievements.len(),
+        },
+    };
+    if history.snapshots.len() == REWIND_TICKS {
+        history.snapshots.pop_front();
+    }
+    history.snapshots.push_back(snapshot);
+}
+
+// Everything that falls is respawned from the snapshot rather than matched
+// up, which both removes anything spawned since and brings back apples
+// caught since. Birds are handled the same way.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn activate_rewind(
+    mut commands: Commands,
+    input: Res<TickInput>,
+    mut history: ResMut<RewindHistory>,
+    falling_query: Query<Entity, Or<(With<Falling>, With<Bird>)>>,
+    mut player_query: Query<(Entity, &mut Transform, &mut PreviousTransform), With<Player>>,
+    mut scoreboard: ResMut<Scoreboard>,
+    mut lives: ResMut<Lives>,
+    mut spawner: ResMut<AppleSpawnerConfig>,
+    mut run_stats: ResMut<RunStats>,
+    textures: Textures,
+    mut profile: ResMut<Profile>,
+    config: Res<GameConfig>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if !input.rewind || history.charges == 0 {
+        return;
+    }
+    let Some(snapshot) = history.snapshots.pop_front() else {
+        return;
+    };
+    history.charges -= 1;
+    history.snapshots.clear();
+
+    for entity in &falling_query {
+        commands.entity(entity).despawn_recursive();
+    }
+    for falling in snapshot.falling {
+        let spawned = match falling.apple {
+            // The rewind pickup itself is left out so a charge can't be farmed.
+            Some((_, Some(PowerUp::Rewind))) => None,
+            Some((kind, power_up)) => spawn_apple(
+                &mut commands,
+                &textures,
+                &profile,
+                &config,
+                &mut game_rng.rng,
+                falling.translation,
+                kind,
+                power_up,
+            ),
+            None => spawn_bomb(&mut commands, &textures, falling.translation),
+        };
+        let Some(entity) = spawned else {
+            continue;
+        };
+        if let Some((velocity, weight)) = falling.motion {
+            commands
+                .entity(entity)
+                .insert((Velocity(velocity), Weight(weight)));
+        }
+        if let Some(timer) = falling.hanging {
+            commands.entity(entity).insert(Hanging(timer));
+        }
+    }
+    for (translation, velocity) in snapshot.birds {
+        spawn_bird(&mut commands, &textures, translation, velocity);
+    }
+    for (player, mut transform, mut fixed) in &mut player_query {
+        transform.translation = snapshot.basket;
+        *fixed = PreviousTransform::new(snapshot.basket);
+        match &snapshot.lit_bomb {
+            Some(lit_bomb) => commands.entity(player).insert(lit_bomb.clone()),
+            None => commands.entity(player).remove::<LitBomb>(),
+        };
+    }
+    scoreboard.score = snapshot.score;
+    scoreboard.combo = snapshot.combo;
+    scoreboard.fractional_points = snapshot.fractional_points;
+    lives.0 = snapshot.lives;
+    spawner.timer.set_elapsed(snapshot.spawn_elapsed);
+
+    // Catches undone here no longer count toward stats or achievements.
+    let stats = snapshot.stats;
+    run_stats.catch_xs.truncate(stats.catches);
+    run_stats.miss_xs.truncate(stats.misses);
+    run_stats.miss_times.truncate(stats.misses);
+    run_stats.life_lost_times.truncate(stats.life_lost);
+    run_stats.frenzy_times.truncate(stats.frenzies);
+    run_stats.best_combo = stats.best_combo;
+    run_stats.new_combo_record = stats.new_combo_record;
+    run_stats.hazards_avoided = stats.hazards_avoided;
+    profile.lifetime_catches = stats.lifetime_catches;
+    profile.best_combo = stats.profile_best_combo;
+    profile.achievements.truncate(stats.achievements);
+}
+
+fn update_rewind_text(
+    history: Res<RewindHistory>,
+    mut text_query: Query<&mut Text, With<RewindText>>,
+) {
+    if !history.is_changed() {
+        return;
+    }
+    for mut text in &mut text_query {
+        text.sections[0].value = match history.charges {
+            0 => String::new(),
+            charges => format!("Rewind x{charges} [R]"),
+        };
+    }
+}
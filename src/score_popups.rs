@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::{
+    despawn_screen, game::NORMAL_APPLE_POINTS, AppleCaughtEvent, GameState, MotionPreferences,
+    PauseMode,
+};
+
+const POPUP_DURATION: f32 = 1.;
+const POPUP_RISE_SPEED: f32 = 60.;
+// Popups from the same frame are stacked so they can all be read.
+const POPUP_STACK_OFFSET: f32 = 22.;
+const BONUS_COLOR: Color = Color::srgb(1., 0.85, 0.2);
+const PENALTY_COLOR: Color = Color::srgb(1., 0.3, 0.3);
+
+#[derive(Component)]
+struct ScorePopup(Timer);
+
+pub fn score_popups_plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        (spawn_popups, animate_popups)
+            .chain()
+            .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+    )
+    .add_systems(OnExit(GameState::Game), despawn_screen::<ScorePopup>);
+}
+
+fn spawn_popups(mut commands: Commands, mut caught_events: EventReader<AppleCaughtEvent>) {
+    for (index, event) in caught_events.read().enumerate() {
+        let (text, color) = match event.points {
+            points if points < 0 => (points.to_string(), PENALTY_COLOR),
+            points if points > NORMAL_APPLE_POINTS => (format!("+{points}"), BONUS_COLOR),
+            points => (format!("+{points}"), Color::WHITE),
+        };
+        let offset = Vec3::new(0., index as f32 * POPUP_STACK_OFFSET, 0.);
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    text,
+                    TextStyle {
+                        font_size: 24.,
+                        color,
+                        ..default()
+                    },
+                ),
+                transform: Transform::from_translation(
+                    event.position.truncate().extend(5.) + offset,
+                ),
+                ..default()
+            },
+            ScorePopup(Timer::from_seconds(POPUP_DURATION, TimerMode::Once)),
+        ));
+    }
+}
+
+fn animate_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    motion_preferences: Res<MotionPreferences>,
+    mut popup_query: Query<(Entity, &mut ScorePopup, &mut Transform, &mut Text)>,
+) {
+    for (entity, mut popup, mut transform, mut text) in &mut popup_query {
+        popup.0.tick(time.delta());
+        if popup.0.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        if !motion_preferences.reduce_motion {
+            transform.translation.y += POPUP_RISE_SPEED * time.delta_seconds();
+        }
+        let alpha = popup.0.fraction_remaining();
+        for section in &mut text.sections {
+            section.style.color.set_alpha(alpha);
+        }
+    }
+}
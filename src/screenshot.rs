@@ -0,0 +1,102 @@
+use std::{
+    fs,
+    path::PathBuf,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+};
+
+use bevy::{
+    prelude::*, render::view::screenshot::ScreenshotManager, utils::SystemTime,
+    window::PrimaryWindow,
+};
+
+use crate::{persistence::DataDir, toasts::Toast, weekly::civil_from_days};
+
+const SCREENSHOT_DIR: &str = "screenshots";
+
+// The frame is only written once it has rendered, so the outcome comes back
+// through a channel.
+#[derive(Resource)]
+struct SavedScreenshots {
+    sender: Sender<Result<PathBuf, String>>,
+    receiver: Mutex<Receiver<Result<PathBuf, String>>>,
+}
+
+pub fn screenshot_plugin(app: &mut App) {
+    let (sender, receiver) = mpsc::channel();
+    app.insert_resource(SavedScreenshots {
+        sender,
+        receiver: Mutex::new(receiver),
+    })
+    .add_systems(Update, (take_screenshot, report_screenshots));
+}
+
+fn take_screenshot(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+    mut screenshot_manager: ResMut<ScreenshotManager>,
+    saved: Res<SavedScreenshots>,
+    data_dir: Res<DataDir>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F12) {
+        return;
+    }
+    let window = match windows.get_single() {
+        Ok(win) => win,
+        Err(_) => return,
+    };
+    let dir = data_dir.0.join(SCREENSHOT_DIR);
+    if let Err(err) = fs::create_dir_all(&dir) {
+        warn!("Failed to create {}: {err}", dir.display());
+        return;
+    }
+    let path = dir.join(format!("applecatcher_{}.png", timestamp()));
+    let sender = saved.sender.clone();
+    let result = screenshot_manager.take_screenshot(window, move |image| {
+        let result = match image.try_into_dynamic() {
+            Ok(image) => image
+                .to_rgb8()
+                .save(&path)
+                .map(|()| path)
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err.to_string()),
+        };
+        // The receiver lives as long as the app, so this only fails on exit.
+        let _ = sender.send(result);
+    });
+    if let Err(err) = result {
+        warn!("Failed to take a screenshot: {err}");
+    }
+}
+
+fn report_screenshots(saved: Res<SavedScreenshots>, mut toasts: EventWriter<Toast>) {
+    let Ok(receiver) = saved.receiver.lock() else {
+        return;
+    };
+    for result in receiver.try_iter() {
+        match result {
+            Ok(path) => {
+                info!("Saved screenshot to {}", path.display());
+                toasts.send(Toast::new("Saved screenshot"));
+            }
+            Err(err) => warn!("Failed to save screenshot: {err}"),
+        }
+    }
+}
+
+// The current UTC time as YYYYMMDD_HHMMSS, without pulling in a date crate.
+fn timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (days, time) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{year:04}{month:02}{day:02}_{:02}{:02}{:02}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
@@ -0,0 +1,439 @@
+use bevy::{ecs::system::SystemParam, prelude::*, ui::FocusPolicy};
+
+use crate::{
+    despawn_screen, monitors::AvailableMonitors, AssistSettings, ControlScheme, DisplaySettings,
+    FrameLimit, MotionPreferences, MovementStyle, SettingsMenu, ShowMagnetAura, TextureQuality,
+    TouchControls, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+const FPS_CAPS: [Option<u32>; 5] = [None, Some(30), Some(60), Some(120), Some(144)];
+
+#[derive(Component)]
+struct OnSettingsMenuScreen;
+
+#[derive(Clone, Copy)]
+enum Setting {
+    ReduceMotion,
+    GameSpeed,
+    BasketSize,
+    FpsCap,
+    Textures,
+    TouchControls,
+    Monitor,
+    MagnetAura,
+    Controls,
+    Movement,
+    VSync,
+    Fullscreen,
+}
+
+impl Setting {
+    const ALL: [Setting; 12] = [
+        Setting::ReduceMotion,
+        Setting::GameSpeed,
+        Setting::BasketSize,
+        Setting::FpsCap,
+        Setting::Textures,
+        Setting::TouchControls,
+        Setting::Monitor,
+        Setting::MagnetAura,
+        Setting::Controls,
+        Setting::Movement,
+        Setting::VSync,
+        Setting::Fullscreen,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Setting::ReduceMotion => "Reduce motion: ",
+            Setting::GameSpeed => "Game speed: ",
+            Setting::BasketSize => "Basket size: ",
+            Setting::FpsCap => "FPS cap: ",
+            Setting::Textures => "Textures: ",
+            Setting::TouchControls => "Touch controls: ",
+            Setting::Monitor => "Monitor: ",
+            Setting::MagnetAura => "Magnet aura: ",
+            Setting::Controls => "Controls: ",
+            Setting::Movement => "Movement: ",
+            Setting::VSync => "VSync: ",
+            Setting::Fullscreen => "Window: ",
+        }
+    }
+}
+
+#[derive(Component)]
+struct SettingValueText(Setting);
+
+#[derive(Component)]
+enum MenuButtonAction {
+    Cycle(Setting),
+    KeyBindings,
+    Audio,
+    Back,
+}
+
+#[derive(SystemParam)]
+struct Settings<'w> {
+    motion_preferences: ResMut<'w, MotionPreferences>,
+    assist_settings: ResMut<'w, AssistSettings>,
+    frame_limit: ResMut<'w, FrameLimit>,
+    texture_quality: ResMut<'w, TextureQuality>,
+    touch_controls: ResMut<'w, TouchControls>,
+    monitors: Res<'w, AvailableMonitors>,
+    show_magnet_aura: ResMut<'w, ShowMagnetAura>,
+    control_scheme: ResMut<'w, ControlScheme>,
+    movement_style: ResMut<'w, MovementStyle>,
+    display_settings: ResMut<'w, DisplaySettings>,
+}
+
+impl Settings<'_> {
+    fn is_changed(&self) -> bool {
+        self.motion_preferences.is_changed()
+            || self.assist_settings.is_changed()
+            || self.frame_limit.is_changed()
+            || self.texture_quality.is_changed()
+            || self.touch_controls.is_changed()
+            || self.monitors.is_changed()
+            || self.show_magnet_aura.is_changed()
+            || self.control_scheme.is_changed()
+            || self.movement_style.is_changed()
+            || self.display_settings.is_changed()
+    }
+
+    fn value(&self, setting: Setting) -> String {
+        match setting {
+            Setting::ReduceMotion => on_off(self.motion_preferences.reduce_motion).to_string(),
+            Setting::GameSpeed => format!("{}%", self.assist_settings.game_speed),
+            Setting::BasketSize => format!("{}%", self.assist_settings.basket_scale),
+            Setting::FpsCap => match self.frame_limit.fps_cap {
+                Some(fps) => fps.to_string(),
+                None => "Unlimited".to_string(),
+            },
+            Setting::Textures => match *self.texture_quality {
+                TextureQuality::Auto => "Auto",
+                TextureQuality::Standard => "Standard",
+                TextureQuality::Hd => "HD",
+            }
+            .to_string(),
+            Setting::TouchControls => match *self.touch_controls {
+                TouchControls::Auto => "Auto",
+                TouchControls::On => "On",
+                TouchControls::Off => "Off",
+            }
+            .to_string(),
+            Setting::Monitor => match self
+                .display_settings
+                .monitor
+                .and_then(|index| Some((index, self.monitors.0.get(index)?)))
+            {
+                Some((index, monitor)) => {
+                    format!("{} ({}x{})", index + 1, monitor.size.x, monitor.size.y)
+                }
+                None => "Primary".to_string(),
+            },
+            Setting::MagnetAura => if self.show_magnet_aura.0 {
+                "Shown"
+            } else {
+                "Hidden"
+            }
+            .to_string(),
+            Setting::Controls => match *self.control_scheme {
+                ControlScheme::Keyboard => "Keyboard",
+                ControlScheme::Mouse => "Mouse",
+            }
+            .to_string(),
+            Setting::Movement => match *self.movement_style {
+                MovementStyle::Smooth => "Smooth",
+                MovementStyle::Instant => "Instant",
+            }
+            .to_string(),
+            Setting::VSync => on_off(self.display_settings.vsync).to_string(),
+            Setting::Fullscreen => if self.display_settings.fullscreen {
+                "Fullscreen"
+            } else {
+                "Windowed"
+            }
+            .to_string(),
+        }
+    }
+
+    fn cycle(&mut self, setting: Setting) {
+        match setting {
+            Setting::ReduceMotion => {
+                self.motion_preferences.reduce_motion = !self.motion_preferences.reduce_motion;
+            }
+            Setting::GameSpeed => {
+                self.assist_settings.game_speed = if self.assist_settings.game_speed <= 50 {
+                    100
+                } else {
+                    self.assist_settings.game_speed - 10
+                };
+            }
+            Setting::BasketSize => {
+                self.assist_settings.basket_scale = if self.assist_settings.basket_scale >= 150 {
+                    80
+                } else {
+                    self.assist_settings.basket_scale + 10
+                };
+            }
+            Setting::FpsCap => {
+                let index = FPS_CAPS
+                    .iter()
+                    .position(|&cap| cap == self.frame_limit.fps_cap)
+                    .unwrap_or(0);
+                self.frame_limit.fps_cap = FPS_CAPS[(index + 1) % FPS_CAPS.len()];
+            }
+            Setting::Textures => {
+                *self.texture_quality = match *self.texture_quality {
+                    TextureQuality::Auto => TextureQuality::Standard,
+                    TextureQuality::Standard => TextureQuality::Hd,
+                    TextureQuality::Hd => TextureQuality::Auto,
+                };
+            }
+            Setting::TouchControls => {
+                *self.touch_controls = match *self.touch_controls {
+                    TouchControls::Auto => TouchControls::On,
+                    TouchControls::On => TouchControls::Off,
+                    TouchControls::Off => TouchControls::Auto,
+                };
+            }
+            Setting::Monitor => {
+                self.display_settings.monitor = match self.display_settings.monitor {
+                    None if !self.monitors.0.is_empty() => Some(0),
+                    Some(index) if index + 1 < self.monitors.0.len() => Some(index + 1),
+                    _ => None,
+                };
+            }
+            Setting::MagnetAura => {
+                self.show_magnet_aura.0 = !self.show_magnet_aura.0;
+            }
+            Setting::Controls => {
+                *self.control_scheme = match *self.control_scheme {
+                    ControlScheme::Keyboard => ControlScheme::Mouse,
+                    ControlScheme::Mouse => ControlScheme::Keyboard,
+                };
+            }
+            Setting::Movement => {
+                *self.movement_style = match *self.movement_style {
+                    MovementStyle::Smooth => MovementStyle::Instant,
+                    MovementStyle::Instant => MovementStyle::Smooth,
+                };
+            }
+            Setting::VSync => {
+                self.display_settings.vsync = !self.display_settings.vsync;
+            }
+            Setting::Fullscreen => {
+                self.display_settings.fullscreen = !self.display_settings.fullscreen;
+            }
+        }
+    }
+}
+
+pub fn settings_menu_plugin(app: &mut App) {
+    app.add_systems(OnEnter(SettingsMenu::Open), setup)
+        .add_systems(
+            Update,
+            (button_system, menu_action, keyboard_input, update_labels)
+                .run_if(in_state(SettingsMenu::Open)),
+        )
+        .add_systems(
+            OnExit(SettingsMenu::Open),
+            despawn_screen::<OnSettingsMenuScreen>,
+        );
+}
+
+fn on_off(value: bool) -> &'static str {
+    if value {
+        "On"
+    } else {
+        "Off"
+    }
+}
+
+fn setup(mut commands: Commands, settings: Settings) {
+    let button_style = Style {
+        width: Val::Px(400.0),
+        height: Val::Px(48.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let button_text_style = TextStyle {
+        font_size: 36.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.05, 0.05, 0.05, 0.95).into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            OnSettingsMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Settings",
+                        TextStyle {
+                            font_size: 60.,
+                            ..default()
+                        },
+                    ));
+
+                    // Settings are laid out in two columns so they fit the window.
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                width: Val::Px(820.0),
+                                flex_wrap: FlexWrap::Wrap,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for setting in Setting::ALL {
+                                parent
+                                    .spawn((
+                                        ButtonBundle {
+                                            style: button_style.clone(),
+                                            background_color: NORMAL_BUTTON.into(),
+                                            ..default()
+                                        },
+                                        MenuButtonAction::Cycle(setting),
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn((
+                                            TextBundle::from_sections([
+                                                TextSection::new(
+                                                    setting.label(),
+                                                    button_text_style.clone(),
+                                                ),
+                                                TextSection::new(
+                                                    settings.value(setting),
+                                                    button_text_style.clone(),
+                                                ),
+                                            ]),
+                                            SettingValueText(setting),
+                                        ));
+                                    });
+                            }
+                        });
+
+                    // The sub-menus and Back share the last row to keep the
+                    // menu within the window height.
+                    let row_button_style = Style {
+                        width: Val::Px(263.0),
+                        ..button_style.clone()
+                    };
+                    parent.spawn(NodeBundle::default()).with_children(|parent| {
+                        for (action, label) in [
+                            (MenuButtonAction::KeyBindings, "Keys"),
+                            (MenuButtonAction::Audio, "Audio"),
+                            (MenuButtonAction::Back, "Back"),
+                        ] {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: row_button_style.clone(),
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    action,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        label,
+                                        button_text_style.clone(),
+                                    ));
+                                });
+                        }
+                    });
+                });
+        });
+}
+
+fn keyboard_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut settings_state: ResMut<NextState<SettingsMenu>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        settings_state.set(SettingsMenu::Closed);
+    }
+}
+
+fn update_labels(settings: Settings, mut value_text_query: Query<(&mut Text, &SettingValueText)>) {
+    if !settings.is_changed() {
+        return;
+    }
+    for (mut text, SettingValueText(setting)) in &mut value_text_query {
+        text.sections[1].value = settings.value(*setting);
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut settings: Settings,
+    mut settings_state: ResMut<NextState<SettingsMenu>>,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Cycle(setting) => {
+                    settings.cycle(*setting);
+                }
+                MenuButtonAction::KeyBindings => {
+                    settings_state.set(SettingsMenu::Controls);
+                }
+                MenuButtonAction::Audio => {
+                    settings_state.set(SettingsMenu::Audio);
+                }
+                MenuButtonAction::Back => {
+                    settings_state.set(SettingsMenu::Closed);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+
+use crate::{
+    assists::run_is_unassisted,
+    cheats::{run_is_clean, Cheats},
+    despawn_screen,
+    persistence::Profile,
+    replay::InputSource,
+    AppleCaughtEvent, AppleMissedEvent, GameState, PauseMode, RunStats, Scoreboard,
+};
+
+pub const SCORE_SAMPLE_INTERVAL: f32 = 1.;
+const RECORD_NOTE_DURATION: f32 = 1.5;
+
+#[derive(Component)]
+struct RecordNote {
+    timer: Timer,
+}
+
+pub fn stats_plugin(app: &mut App) {
+    app.add_systems(
+        FixedPostUpdate,
+        (record_events, sample_score)
+            .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+    )
+    .add_systems(
+        Update,
+        (
+            check_combo_record.run_if(run_is_clean.and_then(run_is_unassisted)),
+            update_record_note,
+        )
+            .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+    )
+    .add_systems(OnExit(GameState::Game), despawn_screen::<RecordNote>);
+}
+
+fn check_combo_record(
+    mut commands: Commands,
+    mut run_stats: ResMut<RunStats>,
+    mut profile: ResMut<Profile>,
+) {
+    if run_stats.best_combo <= profile.best_combo {
+        return;
+    }
+    if profile.best_combo > 0 && !run_stats.new_combo_record {
+        commands.spawn((
+            TextBundle::from_section(
+                "Best combo!",
+                TextStyle {
+                    font_size: 24.,
+                    color: Color::srgb(1., 0.85, 0.2),
+                    ..default()
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(36.),
+                left: Val::Px(0.),
+                ..default()
+            }),
+            RecordNote {
+                timer: Timer::from_seconds(RECORD_NOTE_DURATION, TimerMode::Once),
+            },
+        ));
+    }
+    run_stats.new_combo_record = true;
+    profile.best_combo = run_stats.best_combo;
+}
+
+fn update_record_note(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut note_query: Query<(Entity, &mut RecordNote)>,
+) {
+    for (entity, mut note) in &mut note_query {
+        note.timer.tick(time.delta());
+        if note.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn record_events(
+    mut caught_events: EventReader<AppleCaughtEvent>,
+    mut missed_events: EventReader<AppleMissedEvent>,
+    mut run_stats: ResMut<RunStats>,
+    mut profile: ResMut<Profile>,
+    cheats: Res<Cheats>,
+    input_source: Res<InputSource>,
+) {
+    for event in caught_events.read().filter(|event| event.points >= 0) {
+        run_stats.catch_xs.push(event.position.x);
+        if !cheats.any() && !input_source.is_replay() {
+            profile.lifetime_catches += 1;
+        }
+    }
+    for event in missed_events.read() {
+        run_stats.miss_xs.push(event.position.x);
+        let elapsed = run_stats.elapsed;
+        run_stats.miss_times.push(elapsed);
+    }
+}
+
+fn sample_score(time: Res<Time>, scoreboard: Res<Scoreboard>, mut run_stats: ResMut<RunStats>) {
+    while run_stats.score_samples.len() as f32 * SCORE_SAMPLE_INTERVAL <= run_stats.elapsed {
+        run_stats.score_samples.push(scoreboard.score.max(0) as u32);
+    }
+    run_stats.elapsed += time.delta_seconds();
+}
@@ -0,0 +1,272 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    despawn_screen,
+    persistence::{store_profile, DataDir, HighScore, Profile, PRESTIGE_THRESHOLD},
+    GameState, HOVERED_BUTTON, NORMAL_BUTTON, PRESSED_BUTTON,
+};
+
+#[derive(Component)]
+struct OnStatsScreen;
+
+#[derive(Component)]
+struct PrestigeDialog;
+
+#[derive(Component)]
+enum MenuButtonAction {
+    Prestige,
+    ConfirmPrestige,
+    CancelPrestige,
+    Back,
+}
+
+pub fn stats_screen_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Stats), setup)
+        .add_systems(
+            Update,
+            (button_system, menu_action).run_if(in_state(GameState::Stats)),
+        )
+        .add_systems(OnExit(GameState::Stats), despawn_screen::<OnStatsScreen>);
+}
+
+fn button_style() -> Style {
+    Style {
+        width: Val::Px(250.0),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    }
+}
+
+fn button_text_style() -> TextStyle {
+    TextStyle {
+        font_size: 40.0,
+        ..default()
+    }
+}
+
+fn setup(commands: Commands, profile: Res<Profile>, high_score: Res<HighScore>) {
+    spawn_screen(commands, &profile, &high_score);
+}
+
+fn spawn_screen(mut commands: Commands, profile: &Profile, high_score: &HighScore) {
+    let line_text_style = TextStyle {
+        font_size: 30.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            OnStatsScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Stats",
+                        TextStyle {
+                            font_size: 60.,
+                            ..default()
+                        },
+                    ));
+
+                    for line in [
+                        format!("Lifetime catches: {}", profile.lifetime_catches),
+                        format!("Best combo: {}", profile.best_combo),
+                        format!("Prestige level: {}", profile.prestige_level),
+                        format!(
+                            "High score: {} (prestige {})",
+                            high_score.score, high_score.prestige_level
+                        ),
+                    ] {
+                        parent.spawn(TextBundle::from_section(line, line_text_style.clone()));
+                    }
+
+                    if profile.can_prestige() {
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style(),
+                                    background_color: NORMAL_BUTTON.into(),
+                                    ..default()
+                                },
+                                MenuButtonAction::Prestige,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Prestige",
+                                    button_text_style(),
+                                ));
+                            });
+                    } else {
+                        parent.spawn(TextBundle::from_section(
+                            format!("Prestige unlocks at {} catches", PRESTIGE_THRESHOLD),
+                            line_text_style.clone(),
+                        ));
+                    }
+
+                    parent
+                        .spawn((
+                            ButtonBundle {
+                                style: button_style(),
+                                background_color: NORMAL_BUTTON.into(),
+                                ..default()
+                            },
+                            MenuButtonAction::Back,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section("Back", button_text_style()));
+                        });
+                });
+        });
+}
+
+fn spawn_prestige_dialog(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0., 0., 0., 0.85).into(),
+                focus_policy: FocusPolicy::Block,
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            PrestigeDialog,
+            OnStatsScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(
+                        TextBundle::from_section(
+                            "Reset lifetime catches for a permanent +5% score bonus?",
+                            TextStyle {
+                                font_size: 30.,
+                                ..default()
+                            },
+                        )
+                        .with_text_justify(JustifyText::Center),
+                    );
+
+                    parent.spawn(NodeBundle::default()).with_children(|parent| {
+                        for (action, label) in [
+                            (MenuButtonAction::ConfirmPrestige, "Prestige"),
+                            (MenuButtonAction::CancelPrestige, "Cancel"),
+                        ] {
+                            parent
+                                .spawn((
+                                    ButtonBundle {
+                                        style: button_style(),
+                                        background_color: NORMAL_BUTTON.into(),
+                                        ..default()
+                                    },
+                                    action,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn(TextBundle::from_section(
+                                        label,
+                                        button_text_style(),
+                                    ));
+                                });
+                        }
+                    });
+                });
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn button_system(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        *color = match *interaction {
+            Interaction::Pressed => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn menu_action(
+    interaction_query: Query<
+        (&Interaction, &MenuButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    screen_query: Query<Entity, With<OnStatsScreen>>,
+    dialog_query: Query<Entity, With<PrestigeDialog>>,
+    mut profile: ResMut<Profile>,
+    high_score: Res<HighScore>,
+    dir: Res<DataDir>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut commands: Commands,
+) {
+    for (interaction, menu_button_action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match menu_button_action {
+                MenuButtonAction::Prestige => {
+                    if dialog_query.is_empty() {
+                        spawn_prestige_dialog(commands.reborrow());
+                    }
+                }
+                MenuButtonAction::ConfirmPrestige => {
+                    if profile.can_prestige() {
+                        profile.prestige();
+                        store_profile(&dir, &profile);
+                    }
+                    for entity in &screen_query {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                    spawn_screen(commands.reborrow(), &profile, &high_score);
+                }
+                MenuButtonAction::CancelPrestige => {
+                    for entity in &dialog_query {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                }
+                MenuButtonAction::Back => {
+                    game_state.set(GameState::MainMenu);
+                }
+            }
+        }
+    }
+}
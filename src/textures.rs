@@ -0,0 +1,186 @@
+use bevy::{
+    asset::LoadState,
+    ecs::system::SystemParam,
+    prelude::*,
+    render::texture::{ImageLoaderSettings, ImageSampler},
+    window::PrimaryWindow,
+};
+
+use crate::{GameState, ImageAssets, TextureQuality};
+
+pub const HD_TEXTURE_DIR: &str = "textures/hd/";
+// Must match the `apple_layout` grid in `ImageAssets`.
+pub const APPLE_FRAMES: usize = 4;
+const HD_MIN_WINDOW_HEIGHT: u32 = 1440;
+
+#[derive(Clone, Copy)]
+pub enum Texture {
+    Player,
+    Apple,
+    GoldenApple,
+    RottenApple,
+    Bomb,
+    Background,
+    Canopy,
+    Bird,
+}
+
+impl Texture {
+    fn frame_grid(self) -> UVec2 {
+        match self {
+            Texture::Apple => UVec2::new(APPLE_FRAMES as u32, 1),
+            _ => UVec2::ONE,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Background;
+
+#[derive(Resource)]
+struct HdImageAssets {
+    player: Handle<Image>,
+    apple: Handle<Image>,
+    // Built once the sheet has loaded, as its resolution isn't known up front.
+    apple_layout: Option<Handle<TextureAtlasLayout>>,
+    golden_apple: Handle<Image>,
+    rotten_apple: Handle<Image>,
+    bomb: Handle<Image>,
+    background: Handle<Image>,
+    canopy: Handle<Image>,
+    bird: Handle<Image>,
+}
+
+#[derive(SystemParam)]
+pub struct Textures<'w, 's> {
+    standard: Res<'w, ImageAssets>,
+    hd: Option<Res<'w, HdImageAssets>>,
+    quality: Res<'w, TextureQuality>,
+    asset_server: Res<'w, AssetServer>,
+    images: Res<'w, Assets<Image>>,
+    windows: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl Textures<'_, '_> {
+    // The size is always taken from the standard set so gameplay dimensions
+    // don't depend on which set is being drawn. Sheets report a single frame.
+    pub fn sprite(&self, texture: Texture) -> Option<(Handle<Image>, Vec2)> {
+        let standard = match texture {
+            Texture::Player => &self.standard.player,
+            Texture::Apple => &self.standard.apple,
+            Texture::GoldenApple => &self.standard.golden_apple,
+            Texture::RottenApple => &self.standard.rotten_apple,
+            Texture::Bomb => &self.standard.bomb,
+            Texture::Background => &self.standard.background,
+            Texture::Canopy => &self.standard.canopy,
+            Texture::Bird => &self.standard.bird,
+        };
+        let size = self.images.get(standard)?.size_f32() / texture.frame_grid().as_vec2();
+        match self.hd(texture) {
+            Some(hd) => Some((hd.clone(), size)),
+            None => Some((standard.clone(), size)),
+        }
+    }
+
+    // Matches whichever image `sprite` picked, since the HD sheet has its own grid.
+    pub fn atlas(&self, texture: Texture) -> Option<TextureAtlas> {
+        let layout = match texture {
+            Texture::Apple => match (self.hd(texture), &self.hd) {
+                (Some(_), Some(hd)) => hd.apple_layout.clone()?,
+                _ => self.standard.apple_layout.clone(),
+            },
+            _ => return None,
+        };
+        Some(TextureAtlas { layout, index: 0 })
+    }
+
+    fn hd(&self, texture: Texture) -> Option<&Handle<Image>> {
+        let hd = self.hd.as_ref()?;
+        let handle = match texture {
+            Texture::Player => &hd.player,
+            // Held back until its layout exists so the whole sheet is never drawn.
+            Texture::Apple if hd.apple_layout.is_some() => &hd.apple,
+            Texture::Apple => return None,
+            Texture::GoldenApple => &hd.golden_apple,
+            Texture::RottenApple => &hd.rotten_apple,
+            Texture::Bomb => &hd.bomb,
+            Texture::Background => &hd.background,
+            Texture::Canopy => &hd.canopy,
+            Texture::Bird => &hd.bird,
+        };
+        let loaded = matches!(
+            self.asset_server.get_load_state(handle),
+            Some(LoadState::Loaded)
+        );
+        (self.use_hd() && loaded).then_some(handle)
+    }
+
+    fn use_hd(&self) -> bool {
+        match *self.quality {
+            TextureQuality::Standard => false,
+            TextureQuality::Hd => true,
+            TextureQuality::Auto => self
+                .windows
+                .get_single()
+                .is_ok_and(|window| window.physical_height() >= HD_MIN_WINDOW_HEIGHT),
+        }
+    }
+}
+
+pub fn textures_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Loading), load_hd_textures)
+        .add_systems(
+            Update,
+            (
+                update_background.run_if(resource_exists::<ImageAssets>),
+                build_hd_apple_layout.run_if(resource_exists::<HdImageAssets>),
+            ),
+        );
+}
+
+fn load_hd_textures(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let load = |file: &str| {
+        let path = format!("{HD_TEXTURE_DIR}{file}");
+        asset_server.load_with_settings(path, |settings: &mut ImageLoaderSettings| {
+            settings.sampler = ImageSampler::linear();
+        })
+    };
+    commands.insert_resource(HdImageAssets {
+        player: load("basket.png"),
+        apple: load("apple_sheet.png"),
+        apple_layout: None,
+        golden_apple: load("golden_apple.png"),
+        rotten_apple: load("rotten_apple.png"),
+        bomb: load("bomb.png"),
+        background: load("background.png"),
+        canopy: load("canopy.png"),
+        bird: load("bird.png"),
+    });
+}
+
+fn build_hd_apple_layout(
+    mut hd: ResMut<HdImageAssets>,
+    images: Res<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    if hd.apple_layout.is_some() {
+        return;
+    }
+    let Some(image) = images.get(&hd.apple) else {
+        return;
+    };
+    let grid = Texture::Apple.frame_grid();
+    let layout = TextureAtlasLayout::from_grid(image.size() / grid, grid.x, grid.y, None, None);
+    hd.apple_layout = Some(layouts.add(layout));
+}
+
+fn update_background(textures: Textures, mut query: Query<&mut Handle<Image>, With<Background>>) {
+    let Some((texture, _)) = textures.sprite(Texture::Background) else {
+        return;
+    };
+    for mut handle in &mut query {
+        if *handle != texture {
+            *handle = texture.clone();
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    assists::run_is_unassisted,
+    cheats::run_is_clean,
+    despawn_screen,
+    persistence::{store_profile, DataDir, Profile},
+    AppleCaughtEvent, AppleMissedEvent, GameMode, GameState, PauseMode,
+};
+
+pub const TARGET_CATCHES: u32 = 50;
+pub const SPLIT_INTERVAL: u32 = 10;
+const MISS_PENALTY: Duration = Duration::from_secs(2);
+
+#[derive(Component)]
+struct OnTimeTrialScreen;
+
+#[derive(Component)]
+struct TimeTrialText;
+
+#[derive(Resource, Default)]
+pub struct TimeTrial {
+    pub elapsed: Duration,
+    pub catches: u32,
+    pub splits: Vec<Duration>,
+    pub new_best: bool,
+}
+
+impl TimeTrial {
+    fn finished(&self) -> bool {
+        self.catches >= TARGET_CATCHES
+    }
+}
+
+pub fn time_trial_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Game), setup)
+        .add_systems(
+            FixedUpdate,
+            tick_timer.run_if(
+                in_state(GameState::Game)
+                    .and_then(in_state(PauseMode::Playing))
+                    .and_then(resource_exists::<TimeTrial>),
+            ),
+        )
+        .add_systems(
+            FixedPostUpdate,
+            track_catches.run_if(
+                in_state(GameState::Game)
+                    .and_then(in_state(PauseMode::Playing))
+                    .and_then(resource_exists::<TimeTrial>),
+            ),
+        )
+        .add_systems(
+            Update,
+            update_text.run_if(in_state(GameState::Game).and_then(resource_exists::<TimeTrial>)),
+        )
+        .add_systems(
+            OnExit(GameState::Game),
+            (
+                despawn_screen::<OnTimeTrialScreen>,
+                record_best_time.run_if(
+                    resource_exists::<TimeTrial>
+                        .and_then(run_is_clean)
+                        .and_then(run_is_unassisted),
+                ),
+            ),
+        );
+}
+
+pub fn format_time(duration: Duration) -> String {
+    let centiseconds = duration.as_millis() / 10;
+    format!(
+        "{}:{:02}.{:02}",
+        centiseconds / 6000,
+        centiseconds / 100 % 60,
+        centiseconds % 100
+    )
+}
+
+fn setup(mut commands: Commands, game_mode: Res<GameMode>) {
+    if *game_mode != GameMode::TimeTrial {
+        commands.remove_resource::<TimeTrial>();
+        return;
+    }
+
+    commands.insert_resource(TimeTrial::default());
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(
+                "Time: ",
+                TextStyle {
+                    font_size: 30.,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                format_time(Duration::ZERO),
+                TextStyle {
+                    font_size: 30.,
+                    ..default()
+                },
+            ),
+            TextSection::new(
+                format!("  0/{}", TARGET_CATCHES),
+                TextStyle {
+                    font_size: 30.,
+                    ..default()
+                },
+            ),
+        ])
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.),
+            right: Val::Px(5.),
+            ..default()
+        }),
+        TimeTrialText,
+        OnTimeTrialScreen,
+    ));
+}
+
+fn tick_timer(time: Res<Time>, mut time_trial: ResMut<TimeTrial>) {
+    if !time_trial.finished() {
+        time_trial.elapsed += time.delta();
+    }
+}
+
+fn track_catches(
+    mut caught_events: EventReader<AppleCaughtEvent>,
+    mut missed_events: EventReader<AppleMissedEvent>,
+    mut time_trial: ResMut<TimeTrial>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for _ in missed_events.read() {
+        if !time_trial.finished() {
+            time_trial.elapsed += MISS_PENALTY;
+        }
+    }
+
+    for event in caught_events.read() {
+        if time_trial.finished() {
+            break;
+        }
+        if event.points < 0 {
+            continue;
+        }
+        time_trial.catches += 1;
+        if time_trial.catches.is_multiple_of(SPLIT_INTERVAL) {
+            let elapsed = time_trial.elapsed;
+            time_trial.splits.push(elapsed);
+        }
+        if time_trial.finished() {
+            game_state.set(GameState::Results);
+        }
+    }
+}
+
+fn update_text(time_trial: Res<TimeTrial>, mut text_query: Query<&mut Text, With<TimeTrialText>>) {
+    for mut text in &mut text_query {
+        text.sections[1].value = format_time(time_trial.elapsed);
+        text.sections[2].value = format!("  {}/{}", time_trial.catches, TARGET_CATCHES);
+    }
+}
+
+// Recorded as the run ends, so the results screen can tell a new best apart.
+fn record_best_time(
+    dir: Res<DataDir>,
+    mut time_trial: ResMut<TimeTrial>,
+    mut profile: ResMut<Profile>,
+) {
+    if !time_trial.finished()
+        || profile
+            .best_time
+            .is_some_and(|best_time| best_time <= time_trial.elapsed)
+    {
+        return;
+    }
+    profile.best_time = Some(time_trial.elapsed);
+    store_profile(&dir, &profile);
+    time_trial.new_best = true;
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{despawn_screen, game::Falling, GameMode, GameState, PauseMode};
+
+const RUN_DURATION: Duration = Duration::from_secs(60);
+
+#[derive(Component)]
+struct OnTimedScreen;
+
+#[derive(Component)]
+struct CountdownText;
+
+#[derive(Resource)]
+pub struct RunTimer(Timer);
+
+pub fn timed_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Game), setup)
+        .add_systems(
+            FixedPreUpdate,
+            tick_run_timer.run_if(
+                in_state(GameState::Game)
+                    .and_then(in_state(PauseMode::Playing))
+                    .and_then(resource_exists::<RunTimer>),
+            ),
+        )
+        .add_systems(
+            Update,
+            update_countdown_text
+                .run_if(in_state(GameState::Game).and_then(resource_exists::<RunTimer>)),
+        )
+        .add_systems(OnExit(GameState::Game), despawn_screen::<OnTimedScreen>);
+}
+
+fn format_countdown(remaining: Duration) -> String {
+    let seconds = remaining.as_secs_f32().ceil() as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn setup(mut commands: Commands, game_mode: Res<GameMode>) {
+    if *game_mode != GameMode::Timed {
+        commands.remove_resource::<RunTimer>();
+        return;
+    }
+
+    commands.insert_resource(RunTimer(Timer::new(RUN_DURATION, TimerMode::Once)));
+    commands.spawn((
+        TextBundle::from_section(
+            format_countdown(RUN_DURATION),
+            TextStyle {
+                font_size: 30.,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(0.),
+            right: Val::Px(5.),
+            ..default()
+        }),
+        CountdownText,
+        OnTimedScreen,
+    ));
+}
+
+// Runs before the gameplay systems so apples still in the air when time
+// runs out are gone before they can be caught.
+fn tick_run_timer(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut run_timer: ResMut<RunTimer>,
+    falling_query: Query<Entity, With<Falling>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    run_timer.0.tick(time.delta());
+    if run_timer.0.just_finished() {
+        for entity in &falling_query {
+            commands.entity(entity).despawn_recursive();
+        }
+        game_state.set(GameState::Results);
+    }
+}
+
+fn update_countdown_text(
+    run_timer: Res<RunTimer>,
+    mut text_query: Query<&mut Text, With<CountdownText>>,
+) {
+    for mut text in &mut text_query {
+        text.sections[0].value = format_countdown(run_timer.0.remaining());
+    }
+}
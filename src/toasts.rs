@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+const TOAST_DURATION: f32 = 2.;
+const TOAST_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+// A short message shown in the corner of the screen, whatever the game is doing.
+#[derive(Event)]
+pub struct Toast {
+    text: String,
+    color: Color,
+}
+
+impl Toast {
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            color: TOAST_COLOR,
+        }
+    }
+
+    pub fn with_color(self, color: Color) -> Self {
+        Self { color, ..self }
+    }
+}
+
+// The bottom-left corner of the screen. The combo, the track name and toasts
+// are stacked inside it so they never overlap.
+#[derive(Component)]
+pub struct HudCorner;
+
+#[derive(Component)]
+struct ToastList;
+
+#[derive(Component)]
+struct ToastTimer(Timer);
+
+pub fn toasts_plugin(app: &mut App) {
+    app.add_event::<Toast>()
+        .add_systems(Startup, spawn_toast_list)
+        .add_systems(Update, (show_toasts, expire_toasts));
+}
+
+fn spawn_toast_list(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(10.),
+                    left: Val::Px(10.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexStart,
+                    row_gap: Val::Px(4.),
+                    ..default()
+                },
+                z_index: ZIndex::Global(20),
+                ..default()
+            },
+            HudCorner,
+        ))
+        .with_children(|corner| {
+            corner.spawn((
+                NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    },
+                    ..default()
+                },
+                ToastList,
+            ));
+        });
+}
+
+fn show_toasts(
+    mut commands: Commands,
+    mut toasts: EventReader<Toast>,
+    list_query: Query<Entity, With<ToastList>>,
+) {
+    let Ok(list) = list_query.get_single() else {
+        return;
+    };
+    for toast in toasts.read() {
+        let entity = commands
+            .spawn((
+                TextBundle::from_section(
+                    toast.text.clone(),
+                    TextStyle {
+                        font_size: 24.,
+                        color: toast.color,
+                        ..default()
+                    },
+                ),
+                ToastTimer(Timer::from_seconds(TOAST_DURATION, TimerMode::Once)),
+            ))
+            .id();
+        commands.entity(list).add_child(entity);
+    }
+}
+
+// Real time, so a slowed down game doesn't keep toasts around for longer.
+fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    mut toast_query: Query<(Entity, &mut ToastTimer)>,
+) {
+    for (entity, mut timer) in &mut toast_query {
+        timer.0.tick(time.delta());
+        if timer.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use bevy::{prelude::*, ui::UiSystem, window::PrimaryWindow};
+
+use crate::{
+    actions::ReadActions, despawn_screen, ActionState, GameState, MainCamera, TouchControls,
+};
+
+const BUTTON_COLOR: Color = Color::srgba(1., 1., 1., 0.15);
+const PRESSED_COLOR: Color = Color::srgba(1., 1., 1., 0.35);
+const PAUSE_CORNER_SIZE: f32 = 96.;
+
+#[derive(Component)]
+struct OnTouchOverlay;
+
+#[derive(Component, Clone, Copy)]
+enum TouchButton {
+    Left,
+    Right,
+    Pause,
+}
+
+#[derive(Resource, Default)]
+struct TouchDetected(bool);
+
+// The touch steering the basket. Only the first finger down on the lower
+// half counts, until it is lifted.
+#[derive(Resource, Default)]
+struct DragTouch(Option<u64>);
+
+pub fn touch_controls_plugin(app: &mut App) {
+    app.init_resource::<TouchDetected>()
+        .init_resource::<DragTouch>()
+        .add_systems(
+            PreUpdate,
+            (
+                detect_touch.after(ReadActions),
+                (touch_actions, touch_gestures)
+                    .after(ReadActions)
+                    .after(UiSystem::Focus)
+                    .run_if(in_state(GameState::Game)),
+            ),
+        )
+        .add_systems(Update, sync_overlay.run_if(in_state(GameState::Game)))
+        .add_systems(OnExit(GameState::Game), despawn_screen::<OnTouchOverlay>);
+}
+
+fn detect_touch(touches: Res<Touches>, mut detected: ResMut<TouchDetected>) {
+    if !detected.0 && touches.any_just_pressed() {
+        detected.0 = true;
+    }
+}
+
+fn sync_overlay(
+    mut commands: Commands,
+    touch_controls: Res<TouchControls>,
+    detected: Res<TouchDetected>,
+    overlay_query: Query<Entity, With<OnTouchOverlay>>,
+) {
+    let enabled = match *touch_controls {
+        TouchControls::Auto => detected.0,
+        TouchControls::On => true,
+        TouchControls::Off => false,
+    };
+    match (enabled, overlay_query.is_empty()) {
+        (true, true) => spawn_overlay(&mut commands),
+        (false, false) => {
+            for entity in &overlay_query {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        _ => {}
+    }
+}
+
+// Sized relative to the smaller window dimension so the buttons stay
+// thumb-sized regardless of resolution or UI scale.
+fn spawn_overlay(commands: &mut Commands) {
+    let button_style = |left: Val, right: Val| Style {
+        position_type: PositionType::Absolute,
+        width: Val::VMin(16.),
+        height: Val::VMin(10.),
+        min_width: Val::Px(72.),
+        min_height: Val::Px(48.),
+        left,
+        right,
+        bottom: Val::Px(8.),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let buttons = [
+        (TouchButton::Left, "<", button_style(Val::Px(8.), Val::Auto)),
+        (
+            TouchButton::Right,
+            ">",
+            button_style(Val::Auto, Val::Px(8.)),
+        ),
+        (
+            TouchButton::Pause,
+            "II",
+            Style {
+                margin: UiRect::left(Val::VMin(-8.)),
+                ..button_style(Val::Percent(50.), Val::Auto)
+            },
+        ),
+    ];
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                z_index: ZIndex::Global(5),
+                ..default()
+            },
+            OnTouchOverlay,
+        ))
+        .with_children(|parent| {
+            for (button, label, style) in buttons {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style,
+                            background_color: BUTTON_COLOR.into(),
+                            ..default()
+                        },
+                        button,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            label,
+                            TextStyle {
+                                font_size: 36.,
+                                color: Color::srgba(1., 1., 1., 0.6),
+                                ..default()
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+// Interaction only follows a single pointer, so touches are also hit-tested
+// directly to allow holding a direction while tapping pause.
+fn touch_actions(
+    touches: Res<Touches>,
+    mut actions: ResMut<ActionState>,
+    mut button_query: Query<(
+        &TouchButton,
+        &Interaction,
+        &Node,
+        &GlobalTransform,
+        &mut BackgroundColor,
+    )>,
+) {
+    for (button, interaction, node, transform, mut color) in &mut button_query {
+        let rect = node.logical_rect(transform);
+        let held = *interaction == Interaction::Pressed
+            || touches.iter().any(|touch| rect.contains(touch.position()));
+        let tapped = touches
+            .iter_just_pressed()
+            .any(|touch| rect.contains(touch.position()));
+
+        match button {
+            TouchButton::Left => actions.left |= held,
+            TouchButton::Right => actions.right |= held,
+            TouchButton::Pause => actions.pause |= tapped,
+        }
+        *color = if held { PRESSED_COLOR } else { BUTTON_COLOR }.into();
+    }
+}
+
+// Dragging on the lower half of the screen steers the basket and tapping
+// the top-right corner pauses. Touches that land on the overlay buttons
+// are left to them.
+fn touch_gestures(
+    touches: Res<Touches>,
+    mut drag_touch: ResMut<DragTouch>,
+    mut actions: ResMut<ActionState>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    button_query: Query<(&Node, &GlobalTransform), With<TouchButton>>,
+) {
+    let window = match windows.get_single() {
+        Ok(win) => win,
+        Err(_) => return,
+    };
+    let on_button = |position: Vec2| {
+        button_query
+            .iter()
+            .any(|(node, transform)| node.logical_rect(transform).contains(position))
+    };
+
+    for touch in touches.iter_just_pressed() {
+        let position = touch.position();
+        if on_button(position) {
+            continue;
+        }
+        if position.x >= window.width() - PAUSE_CORNER_SIZE && position.y <= PAUSE_CORNER_SIZE {
+            actions.pause = true;
+        } else if drag_touch.0.is_none() && position.y >= window.height() / 2. {
+            drag_touch.0 = Some(touch.id());
+        }
+    }
+
+    let Some(touch) = drag_touch.0.and_then(|id| touches.get_pressed(id)) else {
+        drag_touch.0 = None;
+        return;
+    };
+    let Ok((camera, camera_transform)) = camera_query.get_single() else {
+        return;
+    };
+    if let Some(world) = camera.viewport_to_world_2d(camera_transform, touch.position()) {
+        actions.drag_x = Some(world.x);
+    }
+}
@@ -0,0 +1,169 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Write},
+    net::TcpStream,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{replay::InputSource, PauseMode, RequestedDrop, SpawnQueue, SpawnRequest};
+
+const IRC_ADDRESS: &str = "irc.chat.twitch.tv:6667";
+const ANONYMOUS_NICKNAME: &str = "justinfan31415";
+const USER_COOLDOWN: f32 = 30.;
+const GLOBAL_COOLDOWN: f32 = 5.;
+const MAX_BACKOFF_SECS: u64 = 60;
+
+// Chat is only read when a channel is set.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TwitchSettings {
+    channel: String,
+    nickname: Option<String>,
+    oauth_token: Option<String>,
+}
+
+struct ChatMessage {
+    viewer: String,
+    drop: RequestedDrop,
+}
+
+#[derive(Resource)]
+struct TwitchChat {
+    receiver: Mutex<Receiver<ChatMessage>>,
+    last_spawn: Option<f32>,
+    last_spawn_by_viewer: HashMap<String, f32>,
+}
+
+pub fn twitch_plugin(app: &mut App) {
+    let config = app.world().resource::<TwitchSettings>().clone();
+    if config.channel.is_empty() {
+        return;
+    }
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || run_connection(config, sender));
+
+    app.insert_resource(TwitchChat {
+        receiver: Mutex::new(receiver),
+        last_spawn: None,
+        last_spawn_by_viewer: HashMap::new(),
+    })
+    .add_systems(Update, drain_chat);
+}
+
+fn run_connection(config: TwitchSettings, sender: Sender<ChatMessage>) {
+    let mut backoff = 1;
+    loop {
+        match read_chat(&config, &sender, &mut backoff) {
+            Ok(()) => return,
+            Err(err) => warn!("Twitch chat disconnected: {err}, retrying in {backoff}s"),
+        }
+        thread::sleep(Duration::from_secs(backoff));
+        backoff = (backoff * 2).min(MAX_BACKOFF_SECS);
+    }
+}
+
+// Returns Ok only once the game has dropped the receiving end.
+fn read_chat(
+    config: &TwitchSettings,
+    sender: &Sender<ChatMessage>,
+    backoff: &mut u64,
+) -> io::Result<()> {
+    let mut stream = TcpStream::connect(IRC_ADDRESS)?;
+    let nickname = config.nickname.as_deref().unwrap_or(ANONYMOUS_NICKNAME);
+    if let Some(token) = &config.oauth_token {
+        write!(
+            stream,
+            "PASS oauth:{}\r\n",
+            token.trim_start_matches("oauth:")
+        )?;
+    }
+    write!(
+        stream,
+        "NICK {nickname}\r\nJOIN #{}\r\n",
+        config.channel.trim_start_matches('#').to_lowercase()
+    )?;
+    *backoff = 1;
+
+    for line in BufReader::new(stream.try_clone()?).lines() {
+        let line = line?;
+        if let Some(server) = line.strip_prefix("PING ") {
+            write!(stream, "PONG {server}\r\n")?;
+        } else if let Some(message) = parse_message(&line) {
+            if sender.send(message).is_err() {
+                return Ok(());
+            }
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "connection closed",
+    ))
+}
+
+fn parse_message(line: &str) -> Option<ChatMessage> {
+    let (prefix, rest) = line.strip_prefix(':')?.split_once(' ')?;
+    let viewer = prefix.split('!').next()?;
+    let (_, text) = rest.strip_prefix("PRIVMSG ")?.split_once(" :")?;
+    let drop = match text.trim() {
+        "!apple" => RequestedDrop::Apple,
+        "!golden" => RequestedDrop::Golden,
+        "!bomb" => RequestedDrop::Bomb,
+        _ => return None,
+    };
+    Some(ChatMessage {
+        viewer: viewer.to_string(),
+        drop,
+    })
+}
+
+fn drain_chat(
+    time: Res<Time<Real>>,
+    pause_mode: Option<Res<State<PauseMode>>>,
+    mut chat: ResMut<TwitchChat>,
+    spawn_queue: Option<ResMut<SpawnQueue>>,
+    input_source: Res<InputSource>,
+) {
+    let messages: Vec<_> = match chat.receiver.get_mut() {
+        Ok(receiver) => receiver.try_iter().collect(),
+        Err(_) => return,
+    };
+    let Some(mut spawn_queue) = spawn_queue else {
+        return;
+    };
+    if !pause_mode.is_some_and(|mode| *mode.get() == PauseMode::Playing) {
+        return;
+    }
+    // A replay drops what chat asked for when it was recorded.
+    if input_source.is_replay() {
+        return;
+    }
+
+    let now = time.elapsed_seconds();
+    for message in messages {
+        let on_cooldown = chat
+            .last_spawn
+            .is_some_and(|last| now - last < GLOBAL_COOLDOWN)
+            || chat
+                .last_spawn_by_viewer
+                .get(&message.viewer)
+                .is_some_and(|&last| now - last < USER_COOLDOWN);
+        if on_cooldown {
+            continue;
+        }
+        chat.last_spawn = Some(now);
+        chat.last_spawn_by_viewer
+            .insert(message.viewer.clone(), now);
+        spawn_queue.0.push_back(SpawnRequest {
+            viewer: message.viewer,
+            drop: message.drop,
+        });
+    }
+}
@@ -0,0 +1,289 @@
+use std::fmt;
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+};
+use bevy_asset_loader::asset_collection::AssetCollection;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::{
+    game::{spawn_apple, spawn_x_range, spawn_y, AppleKind},
+    persistence::Profile,
+    textures::{Texture, Textures},
+    GameConfig, GameMode, GameRng, GameState, PauseMode, PlayArea, PowerUp,
+};
+
+const FIRST_WAVE_DELAY: f32 = 30.;
+const WAVE_INTERVAL: (f32, f32) = (45., 75.);
+
+#[derive(AssetCollection, Resource)]
+pub struct WaveAssets {
+    #[asset(
+        paths(
+            "waves/sweep.wave.ron",
+            "waves/v_formation.wave.ron",
+            "waves/alternating_edges.wave.ron",
+            "waves/apple_rain.wave.ron"
+        ),
+        collection(typed)
+    )]
+    pub waves: Vec<Handle<Wave>>,
+}
+
+#[derive(Deserialize)]
+enum WaveX {
+    Position(f32),
+    Lane(String),
+}
+
+fn default_kind() -> String {
+    "apple".to_string()
+}
+
+#[derive(Deserialize)]
+struct WaveEntry {
+    time: f32,
+    x: WaveX,
+    #[serde(default = "default_kind")]
+    kind: String,
+}
+
+#[derive(Deserialize)]
+struct WaveFile {
+    #[serde(default)]
+    replaces_random: bool,
+    spawns: Vec<WaveEntry>,
+}
+
+struct WaveSpawn {
+    time: f32,
+    // -1 is the left edge of the spawn range and 1 the right edge.
+    x: f32,
+    apple_kind: AppleKind,
+    power_up: Option<PowerUp>,
+}
+
+#[derive(Asset, TypePath)]
+pub struct Wave {
+    replaces_random: bool,
+    spawns: Vec<WaveSpawn>,
+}
+
+#[derive(Debug)]
+pub enum WaveLoadError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for WaveLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WaveLoadError::Io(err) => write!(f, "could not read wave: {err}"),
+            WaveLoadError::Ron(err) => write!(f, "could not parse wave: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for WaveLoadError {}
+
+#[derive(Default)]
+struct WaveLoader;
+
+impl AssetLoader for WaveLoader {
+    type Asset = Wave;
+    type Settings = ();
+    type Error = WaveLoadError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Wave, WaveLoadError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(WaveLoadError::Io)?;
+        let file: WaveFile = ron::de::from_bytes(&bytes).map_err(WaveLoadError::Ron)?;
+
+        let path = load_context.path().display().to_string();
+        let mut spawns: Vec<_> = file
+            .spawns
+            .into_iter()
+            .filter_map(|entry| parse_spawn(entry, &path))
+            .collect();
+        spawns.sort_by(|a, b| a.time.total_cmp(&b.time));
+        Ok(Wave {
+            replaces_random: file.replaces_random,
+            spawns,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["wave.ron"]
+    }
+}
+
+fn parse_spawn(entry: WaveEntry, path: &str) -> Option<WaveSpawn> {
+    let (apple_kind, power_up) = match entry.kind.as_str() {
+        "apple" => (AppleKind::Normal, None),
+        "golden" => (AppleKind::Golden, None),
+        "rotten" => (AppleKind::Rotten, None),
+        "rewind" => (AppleKind::Normal, Some(PowerUp::Rewind)),
+        "widen" => (AppleKind::Normal, Some(PowerUp::Widen)),
+        "slow" => (AppleKind::Normal, Some(PowerUp::SlowMotion)),
+        "star" => (AppleKind::Normal, Some(PowerUp::Star)),
+        kind => {
+            warn!("Skipping spawn with unknown kind {kind:?} in {path}");
+            return None;
+        }
+    };
+    let x = match entry.x {
+        WaveX::Position(x) => x.clamp(-1., 1.),
+        WaveX::Lane(lane) => match lane.as_str() {
+            "left" => -1.,
+            "left-center" => -0.5,
+            "center" => 0.,
+            "right-center" => 0.5,
+            "right" => 1.,
+            lane => {
+                warn!("Skipping spawn with unknown lane {lane:?} in {path}");
+                return None;
+            }
+        },
+    };
+    Some(WaveSpawn {
+        time: entry.time,
+        x,
+        apple_kind,
+        power_up,
+    })
+}
+
+struct ActiveWave {
+    wave: Handle<Wave>,
+    replaces_random: bool,
+    elapsed: f32,
+    next: usize,
+}
+
+#[derive(Resource)]
+pub struct WavePlayer {
+    active: Option<ActiveWave>,
+    cooldown: Timer,
+}
+
+impl Default for WavePlayer {
+    fn default() -> Self {
+        Self {
+            active: None,
+            cooldown: Timer::from_seconds(FIRST_WAVE_DELAY, TimerMode::Once),
+        }
+    }
+}
+
+impl WavePlayer {
+    pub fn replaces_random(&self) -> bool {
+        self.active
+            .as_ref()
+            .is_some_and(|active| active.replaces_random)
+    }
+
+    pub fn start(&mut self, wave: Handle<Wave>, waves: &Assets<Wave>) {
+        let replaces_random = waves.get(&wave).is_some_and(|wave| wave.replaces_random);
+        self.active = Some(ActiveWave {
+            wave,
+            replaces_random,
+            elapsed: 0.,
+            next: 0,
+        });
+    }
+}
+
+pub fn waves_plugin(app: &mut App) {
+    app.init_asset::<Wave>()
+        .init_asset_loader::<WaveLoader>()
+        .add_systems(
+            FixedUpdate,
+            (trigger_random_wave, play_wave)
+                .chain()
+                .run_if(in_state(GameState::Game).and_then(in_state(PauseMode::Playing))),
+        );
+}
+
+fn trigger_random_wave(
+    time: Res<Time>,
+    game_mode: Res<GameMode>,
+    wave_assets: Res<WaveAssets>,
+    waves: Res<Assets<Wave>>,
+    mut wave_player: ResMut<WavePlayer>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    if *game_mode != GameMode::Endless
+        || wave_player.active.is_some()
+        || wave_assets.waves.is_empty()
+    {
+        return;
+    }
+    wave_player.cooldown.tick(time.delta());
+    if !wave_player.cooldown.finished() {
+        return;
+    }
+
+    let index = game_rng.rng.gen_range(0..wave_assets.waves.len());
+    let delay = game_rng.rng.gen_range(WAVE_INTERVAL.0..=WAVE_INTERVAL.1);
+    wave_player.cooldown = Timer::from_seconds(delay, TimerMode::Once);
+    wave_player.start(wave_assets.waves[index].clone(), &waves);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn play_wave(
+    mut commands: Commands,
+    time: Res<Time>,
+    waves: Res<Assets<Wave>>,
+    mut wave_player: ResMut<WavePlayer>,
+    textures: Textures,
+    profile: Res<Profile>,
+    config: Res<GameConfig>,
+    play_area: Res<PlayArea>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let Some(active) = wave_player.active.as_mut() else {
+        return;
+    };
+    let Some(wave) = waves.get(&active.wave) else {
+        wave_player.active = None;
+        return;
+    };
+    let texture_size = match textures.sprite(Texture::Apple) {
+        Some((_, size)) => size,
+        None => return,
+    };
+    let top = spawn_y(play_area.height, texture_size.y, config.apple_scale);
+    let spawn_range = spawn_x_range(play_area.width, texture_size.x, config.apple_scale);
+
+    active.elapsed += time.delta_seconds();
+    while let Some(spawn) = wave.spawns.get(active.next) {
+        if spawn.time > active.elapsed {
+            break;
+        }
+        let translation = Vec3::new(spawn.x * spawn_range, top, 0.);
+        spawn_apple(
+            &mut commands,
+            &textures,
+            &profile,
+            &config,
+            &mut game_rng.rng,
+            translation,
+            spawn.apple_kind,
+            spawn.power_up,
+        );
+        active.next += 1;
+    }
+    if active.next >= wave.spawns.len() {
+        wave_player.active = None;
+    }
+}
@@ -0,0 +1,210 @@
+use bevy::{prelude::*, utils::SystemTime};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    assists::run_is_unassisted, cheats::run_is_clean, despawn_screen, persistence::Profile,
+    GameState, Scoreboard,
+};
+
+const KEPT_WEEKS: i64 = 8;
+const FAST_APPLES_SCALE: f32 = 1.4;
+const DOWNPOUR_INTERVAL_SCALE: f32 = 0.6;
+const FOG_HEIGHT: f32 = 40.;
+const FOG_COLOR: Color = Color::srgba(0.75, 0.78, 0.8, 0.85);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Mutator {
+    FastApples,
+    Fog,
+    Downpour,
+}
+
+impl Mutator {
+    fn name(self) -> &'static str {
+        match self {
+            Mutator::FastApples => "Fast Apples",
+            Mutator::Fog => "Fog",
+            Mutator::Downpour => "Downpour",
+        }
+    }
+}
+
+const MUTATOR_SETS: [[Mutator; 2]; 3] = [
+    [Mutator::FastApples, Mutator::Fog],
+    [Mutator::Fog, Mutator::Downpour],
+    [Mutator::Downpour, Mutator::FastApples],
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub struct WeekId {
+    year: i32,
+    week: u32,
+}
+
+impl WeekId {
+    pub fn current() -> Self {
+        let days = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|elapsed| (elapsed.as_secs() / 86_400) as i64)
+            .unwrap_or_default();
+        Self::from_days(days)
+    }
+
+    // ISO 8601 weeks start on Monday and belong to the year their Thursday falls in.
+    fn from_days(days: i64) -> Self {
+        let thursday = days - weekday(days) + 3;
+        let (year, ..) = civil_from_days(thursday);
+        let week = ((thursday - days_from_civil(year, 1, 1)) / 7 + 1) as u32;
+        Self { year, week }
+    }
+
+    fn monday(self) -> i64 {
+        let january_fourth = days_from_civil(self.year, 1, 4);
+        january_fourth - weekday(january_fourth) + 7 * (self.week as i64 - 1)
+    }
+
+    fn weeks_since(self, earlier: WeekId) -> i64 {
+        (self.monday() - earlier.monday()) / 7
+    }
+
+    pub fn seed(self) -> u64 {
+        self.year as u64 * 100 + self.week as u64
+    }
+
+    pub fn mutators(self) -> [Mutator; 2] {
+        MUTATOR_SETS[(self.monday() / 7).rem_euclid(MUTATOR_SETS.len() as i64) as usize]
+    }
+
+    pub fn mutator_names(self) -> String {
+        self.mutators().map(Mutator::name).join(" + ")
+    }
+}
+
+// Days since 1970-01-01 (a Thursday), Monday = 0.
+fn weekday(days: i64) -> i64 {
+    (days + 3).rem_euclid(7)
+}
+
+// The year, month and day of a count of days since 1970-01-01, after Howard
+// Hinnant's `civil_from_days`.
+pub fn civil_from_days(days: i64) -> (i32, u32, u32) {
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    // Years in this calendar start in March, so January and February belong to the next one.
+    let (month, year) = if month_index < 10 {
+        (month_index + 3, year_of_era + era * 400)
+    } else {
+        (month_index - 9, year_of_era + era * 400 + 1)
+    };
+    (year as i32, month as u32, day)
+}
+
+fn days_from_civil(year: i32, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[derive(Resource, Default)]
+pub struct ActiveMutators(Vec<Mutator>);
+
+impl ActiveMutators {
+    pub fn fall_speed_scale(&self) -> f32 {
+        if self.0.contains(&Mutator::FastApples) {
+            FAST_APPLES_SCALE
+        } else {
+            1.
+        }
+    }
+
+    pub fn spawn_interval_scale(&self) -> f32 {
+        if self.0.contains(&Mutator::Downpour) {
+            DOWNPOUR_INTERVAL_SCALE
+        } else {
+            1.
+        }
+    }
+}
+
+// The week a weekly run started in, so its score never lands in another week.
+// Set by `start_run`, or from the replay being watched.
+#[derive(Resource)]
+pub struct WeeklyRun(pub WeekId);
+
+#[derive(Component)]
+struct Fog;
+
+pub fn weekly_plugin(app: &mut App) {
+    app.init_resource::<ActiveMutators>()
+        .add_systems(OnEnter(GameState::Game), setup_run)
+        .add_systems(
+            Update,
+            record_weekly_best.run_if(
+                in_state(GameState::Game)
+                    .and_then(resource_exists::<WeeklyRun>)
+                    .and_then(resource_changed::<Scoreboard>)
+                    .and_then(run_is_clean)
+                    .and_then(run_is_unassisted),
+            ),
+        )
+        .add_systems(OnExit(GameState::Game), despawn_screen::<Fog>);
+}
+
+fn setup_run(
+    mut commands: Commands,
+    weekly_run: Option<Res<WeeklyRun>>,
+    mut mutators: ResMut<ActiveMutators>,
+) {
+    let Some(weekly_run) = weekly_run else {
+        mutators.0.clear();
+        return;
+    };
+    mutators.0 = weekly_run.0.mutators().to_vec();
+
+    if mutators.0.contains(&Mutator::Fog) {
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(0.),
+                    width: Val::Percent(100.),
+                    height: Val::Percent(FOG_HEIGHT),
+                    ..default()
+                },
+                background_color: FOG_COLOR.into(),
+                z_index: ZIndex::Global(-1),
+                ..default()
+            },
+            Fog,
+        ));
+    }
+}
+
+fn record_weekly_best(
+    weekly_run: Res<WeeklyRun>,
+    scoreboard: Res<Scoreboard>,
+    mut profile: ResMut<Profile>,
+) {
+    let week = weekly_run.0;
+    let score = scoreboard.score.max(0) as u32;
+    if profile
+        .weekly_best
+        .get(&week)
+        .is_some_and(|&best| best >= score)
+    {
+        return;
+    }
+    profile.weekly_best.insert(week, score);
+    profile
+        .weekly_best
+        .retain(|&other, _| week.weeks_since(other) < KEPT_WEEKS);
+}
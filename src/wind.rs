@@ -0,0 +1,172 @@
+use std::{f32::consts::PI, time::Duration};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    despawn_screen,
+    game::{spawn_x_range, Falling, GameTick, Player, Velocity},
+    textures::{Texture, Textures},
+    GameConfig, GameRng, GameState, PauseMode, PlayArea, TimeDilation,
+};
+
+const FIRST_GUST_DELAY: f32 = 15.;
+const GUST_INTERVAL: (f32, f32) = (10., 20.);
+const GUST_DURATION: f32 = 4.;
+// Sideways speed at the peak of a gust, in pixels per second.
+const GUST_SPEED: (f32, f32) = (60., 180.);
+// How quickly an apple's drift catches up with the wind.
+const WIND_DRAG: f32 = 2.;
+const MAX_ARROWS: f32 = 3.;
+const WIND_COLOR: Color = Color::srgb(0.7, 0.9, 1.);
+
+#[derive(Component)]
+struct OnWindScreen;
+
+#[derive(Component)]
+struct WindText;
+
+struct Gust {
+    elapsed: f32,
+    // Negative blows to the left.
+    peak: f32,
+}
+
+#[derive(Resource)]
+pub struct Wind {
+    calm: Timer,
+    gust: Option<Gust>,
+}
+
+impl Default for Wind {
+    fn default() -> Self {
+        Self {
+            calm: Timer::from_seconds(FIRST_GUST_DELAY, TimerMode::Once),
+            gust: None,
+        }
+    }
+}
+
+impl Wind {
+    // Ramps up and back down over the length of the gust.
+    fn speed(&self) -> f32 {
+        self.gust.as_ref().map_or(0., |gust| {
+            gust.peak * (PI * gust.elapsed / GUST_DURATION).sin()
+        })
+    }
+}
+
+pub fn wind_plugin(app: &mut App) {
+    app.add_systems(OnEnter(GameState::Game), setup)
+        .add_systems(
+            FixedUpdate,
+            (update_wind, push_apples)
+                .chain()
+                // Applied after the apples move so none is ever drawn past an edge.
+                .after(GameTick)
+                .run_if(
+                    in_state(GameState::Game)
+                        .and_then(in_state(PauseMode::Playing))
+                        .and_then(any_with_component::<Player>),
+                ),
+        )
+        .add_systems(Update, update_wind_text.run_if(in_state(GameState::Game)))
+        .add_systems(OnExit(GameState::Game), despawn_screen::<OnWindScreen>);
+}
+
+fn setup(mut commands: Commands) {
+    commands.insert_resource(Wind::default());
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 26.,
+                color: WIND_COLOR,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(40.),
+            right: Val::Px(5.),
+            ..default()
+        }),
+        WindText,
+        OnWindScreen,
+    ));
+}
+
+fn update_wind(
+    time: Res<Time>,
+    time_dilation: Res<TimeDilation>,
+    mut wind: ResMut<Wind>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    let delta = time.delta_seconds() * time_dilation.scale();
+    if let Some(gust) = &mut wind.gust {
+        gust.elapsed += delta;
+        if gust.elapsed >= GUST_DURATION {
+            wind.gust = None;
+        }
+        return;
+    }
+    wind.calm.tick(Duration::from_secs_f32(delta));
+    if !wind.calm.finished() {
+        return;
+    }
+    let rng = &mut game_rng.rng;
+    let speed = rng.gen_range(GUST_SPEED.0..=GUST_SPEED.1);
+    let peak = if rng.gen_bool(0.5) { speed } else { -speed };
+    let calm = rng.gen_range(GUST_INTERVAL.0..=GUST_INTERVAL.1);
+    wind.gust = Some(Gust { elapsed: 0., peak });
+    wind.calm = Timer::from_seconds(calm, TimerMode::Once);
+}
+
+// Apples are stopped at the edges of the spawn range rather than blown off
+// the screen.
+fn push_apples(
+    time: Res<Time>,
+    time_dilation: Res<TimeDilation>,
+    wind: Res<Wind>,
+    textures: Textures,
+    config: Res<GameConfig>,
+    play_area: Res<PlayArea>,
+    mut falling_query: Query<(&mut Transform, &mut Velocity), With<Falling>>,
+) {
+    let Some((_, texture_size)) = textures.sprite(Texture::Apple) else {
+        return;
+    };
+    let range = spawn_x_range(play_area.width, texture_size.x, config.apple_scale);
+    let delta = time.delta_seconds() * time_dilation.scale();
+    let catch_up = 1. - (-WIND_DRAG * delta).exp();
+    let speed = wind.speed();
+    for (mut transform, mut velocity) in &mut falling_query {
+        velocity.0.x += (speed - velocity.0.x) * catch_up;
+        let x = transform.translation.x;
+        if x.abs() > range {
+            transform.translation.x = x.clamp(-range, range);
+            if velocity.0.x * x > 0. {
+                velocity.0.x = 0.;
+            }
+        }
+    }
+}
+
+// More arrows for a stronger gust, pointing the way it blows.
+fn update_wind_text(wind: Option<Res<Wind>>, mut text_query: Query<&mut Text, With<WindText>>) {
+    let Some(wind) = wind else {
+        return;
+    };
+    let speed = wind.speed();
+    let arrows = (speed.abs() / GUST_SPEED.1 * MAX_ARROWS).round() as usize;
+    let label = match arrows {
+        0 => String::new(),
+        _ if speed < 0. => format!("Wind {}", "<".repeat(arrows)),
+        _ => format!("Wind {}", ">".repeat(arrows)),
+    };
+    for mut text in &mut text_query {
+        if text.sections[0].value != label {
+            text.sections[0].value.clone_from(&label);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+//! A headless copy of the game on `MinimalPlugins`, with in-memory stand-ins
+//! for the assets the loading state would normally provide.
+
+#![allow(dead_code)]
+
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use applecatcher::{
+    actions::ReadActions,
+    applecatcher_plugin,
+    audio::AudioAssets,
+    config::GameConfig,
+    game::{spawn_apple, AppleKind, Player},
+    main_menu::MenuButtonAction,
+    music::MusicDir,
+    persistence::{DataDir, Profile},
+    textures::Textures,
+    waves::WaveAssets,
+    ActionState, FontAssets, GameState, ImageAssets, PauseMode,
+};
+use bevy::{
+    a11y::AccessibilityPlugin,
+    ecs::system::RunSystemOnce,
+    input::InputPlugin,
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+    state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+    window::ExitCondition,
+};
+
+pub const TICK: Duration = Duration::from_nanos(1_000_000_000 / 60);
+// Frames to wait for something that should happen within a few seconds.
+const PATIENCE: usize = 600;
+
+/// Overrides whatever the keyboard and gamepads produced this frame. Remove it
+/// to drive the game from input events instead.
+#[derive(Resource, Default)]
+pub struct ScriptedActions(pub ActionState);
+
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        StatesPlugin,
+        AssetPlugin::default(),
+        InputPlugin,
+        AccessibilityPlugin,
+        WindowPlugin {
+            primary_window: None,
+            exit_condition: ExitCondition::DontExit,
+            close_when_requested: false,
+        },
+    ))
+    .init_asset::<Image>()
+    .init_asset::<TextureAtlasLayout>()
+    .init_asset::<Mesh>()
+    .init_asset::<ColorMaterial>()
+    .init_asset::<AudioSource>()
+    .init_resource::<GlobalVolume>()
+    .insert_resource(TimeUpdateStrategy::ManualDuration(TICK))
+    .insert_resource(DataDir(temp_data_dir()))
+    .insert_resource(MusicDir(temp_data_dir()))
+    .add_plugins(applecatcher_plugin)
+    .init_resource::<ScriptedActions>()
+    .add_systems(
+        PreUpdate,
+        apply_scripted_actions
+            .after(ReadActions)
+            .run_if(resource_exists::<ScriptedActions>),
+    );
+    insert_fake_assets(app.world_mut());
+    app
+}
+
+// Every app saves into an empty directory of its own, so tests running in
+// parallel don't share save files or pick up what earlier runs left. The
+// music folder is one too, so no tracks are loaded.
+fn temp_data_dir() -> PathBuf {
+    static NEXT: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "applecatcher-test-{}-{}",
+        std::process::id(),
+        NEXT.fetch_add(1, Ordering::Relaxed)
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("the temp dir is writable");
+    dir
+}
+
+fn apply_scripted_actions(script: Res<ScriptedActions>, mut actions: ResMut<ActionState>) {
+    *actions = script.0.clone();
+}
+
+// Blank images with the real textures' sizes, since gameplay reads sizes
+// from the images themselves.
+fn insert_fake_assets(world: &mut World) {
+    let mut images = world.resource_mut::<Assets<Image>>();
+    let mut image = |width, height| {
+        images.add(Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255; 4],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        ))
+    };
+    let image_assets = ImageAssets {
+        player: image(128, 69),
+        apple_layout: Handle::default(),
+        apple: image(512, 128),
+        golden_apple: image(128, 128),
+        rotten_apple: image(128, 128),
+        bomb: image(128, 128),
+        background: image(1280, 720),
+        canopy: image(1280, 160),
+        bird: image(96, 64),
+    };
+    let apple_layout =
+        world
+            .resource_mut::<Assets<TextureAtlasLayout>>()
+            .add(TextureAtlasLayout::from_grid(
+                UVec2::splat(128),
+                4,
+                1,
+                None,
+                None,
+            ));
+    world.insert_resource(ImageAssets {
+        apple_layout,
+        ..image_assets
+    });
+    world.insert_resource(FontAssets {
+        bold: Handle::default(),
+    });
+    world.insert_resource(WaveAssets { waves: Vec::new() });
+    world.insert_resource(AudioAssets {
+        catch: Handle::default(),
+        miss: Handle::default(),
+        click: Handle::default(),
+        menu_theme: Handle::default(),
+    });
+}
+
+pub fn step(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+pub fn step_until(app: &mut App, mut done: impl FnMut(&mut App) -> bool) {
+    for _ in 0..PATIENCE {
+        if done(app) {
+            return;
+        }
+        app.update();
+    }
+    panic!("gave up after {PATIENCE} frames");
+}
+
+pub fn game_state(app: &App) -> GameState {
+    app.world().resource::<State<GameState>>().get().clone()
+}
+
+pub fn pause_mode(app: &App) -> Option<PauseMode> {
+    app.world()
+        .get_resource::<State<PauseMode>>()
+        .map(|state| state.get().clone())
+}
+
+/// Waits for the config to load, as the loading state would, then carries
+/// on to the main menu.
+pub fn boot(app: &mut App) {
+    step_until(app, |app| {
+        !app.world().resource::<Assets<GameConfig>>().is_empty()
+    });
+    app.world_mut()
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::Loaded);
+    step_until(app, |app| game_state(app) == GameState::MainMenu);
+}
+
+/// Presses the first button matching `is_button`, as a click would.
+pub fn click<T: Component>(app: &mut App, is_button: impl Fn(&T) -> bool) {
+    let world = app.world_mut();
+    let button = world
+        .query::<(Entity, &T)>()
+        .iter(world)
+        .find(|(_, action)| is_button(action))
+        .map(|(entity, _)| entity)
+        .expect("the button is on screen");
+    world.entity_mut(button).insert(Interaction::Pressed);
+    app.update();
+}
+
+/// Clicks Play on the main menu and waits out the countdown.
+pub fn press_play(app: &mut App) {
+    click(app, |action| matches!(action, MenuButtonAction::Play));
+    step_until(app, |app| pause_mode(app) == Some(PauseMode::Playing));
+}
+
+/// Holds the pause action for a single frame.
+pub fn press_pause(app: &mut App) {
+    app.world_mut().resource_mut::<ScriptedActions>().0.pause = true;
+    app.update();
+    app.world_mut().resource_mut::<ScriptedActions>().0.pause = false;
+    app.update();
+}
+
+pub fn start_game() -> App {
+    let mut app = headless_app();
+    boot(&mut app);
+    press_play(&mut app);
+    app
+}
+
+pub fn player_translation(app: &mut App) -> Vec3 {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Transform, With<Player>>()
+        .single(world)
+        .translation
+}
+
+pub fn spawn_test_apple(app: &mut App, translation: Vec3) -> Entity {
+    app.world_mut().run_system_once(
+        move |mut commands: Commands,
+              textures: Textures,
+              profile: Res<Profile>,
+              config: Res<GameConfig>| {
+            spawn_apple(
+                &mut commands,
+                &textures,
+                &profile,
+                &config,
+                &mut rand::thread_rng(),
+                translation,
+                AppleKind::Normal,
+                None,
+            )
+            .expect("the fake apple texture is loaded")
+        },
+    )
+}
+
+pub fn exists(app: &App, entity: Entity) -> bool {
+    app.world().get_entity(entity).is_some()
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use applecatcher::{config::GameConfig, game::Falling};
+use bevy::prelude::*;
+
+use common::*;
+
+const SEED: u64 = 99;
+const RUN_FRAMES: usize = 15 * 60;
+
+// Every falling thing on every frame, including its spin and animation frame.
+#[derive(Resource, Default)]
+struct Trace(Vec<(Transform, Option<usize>)>);
+
+fn record_falling(
+    query: Query<(&Transform, Option<&TextureAtlas>), With<Falling>>,
+    mut trace: ResMut<Trace>,
+) {
+    trace.0.extend(
+        query
+            .iter()
+            .map(|(transform, atlas)| (*transform, atlas.map(|atlas| atlas.index))),
+    );
+}
+
+fn run_with_seed(seed: u64) -> Vec<(Transform, Option<usize>)> {
+    let mut app = headless_app();
+    app.init_resource::<Trace>()
+        .add_systems(PostUpdate, record_falling);
+    boot(&mut app);
+    app.world_mut().resource_mut::<GameConfig>().seed = Some(seed);
+    press_play(&mut app);
+    step(&mut app, RUN_FRAMES);
+    app.world_mut().remove_resource::<Trace>().unwrap().0
+}
+
+#[test]
+fn the_same_seed_plays_out_the_same() {
+    let first = run_with_seed(SEED);
+    let second = run_with_seed(SEED);
+
+    assert!(!first.is_empty());
+    assert_eq!(first, second);
+}
+
+#[test]
+fn another_seed_plays_out_differently() {
+    assert_ne!(run_with_seed(SEED), run_with_seed(SEED + 1));
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use std::time::Duration;
+
+use applecatcher::{config::GameConfig, game::Velocity, Scoreboard};
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use common::*;
+
+// Half the basket's height per tick, so only the fixed steps keep it from
+// jumping clean over the basket in one long frame.
+const FAST_FALL: f32 = 1800.;
+
+#[derive(Resource, Default)]
+struct FixedTicks(u32);
+
+fn count_fixed_ticks(mut ticks: ResMut<FixedTicks>) {
+    ticks.0 += 1;
+}
+
+#[test]
+fn a_two_second_frame_is_clamped_and_stepped_without_tunnelling() {
+    let mut app = start_game();
+    app.init_resource::<FixedTicks>()
+        .add_systems(FixedUpdate, count_fixed_ticks);
+    let basket = player_translation(&mut app);
+    let apple = spawn_test_apple(&mut app, basket + Vec3::Y * 80.);
+    app.world_mut()
+        .entity_mut(apple)
+        .insert(Velocity(Vec2::new(0., -FAST_FALL)));
+    let score = app.world().resource::<Scoreboard>().score;
+    let player_speed = app.world().resource::<GameConfig>().player_speed;
+    app.world_mut().resource_mut::<ScriptedActions>().0.right = true;
+
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs(2)));
+    app.update();
+
+    let max_delta = Duration::from_nanos(1_000_000_000 / 15);
+    assert_eq!(app.world().resource::<Time<Virtual>>().delta(), max_delta);
+    let ticks = app.world().resource::<FixedTicks>().0;
+    assert!((4..=5).contains(&ticks));
+    // The basket moves on fixed ticks, which may carry part of a tick over
+    // from the frame before the clamped one.
+    let moved = (player_translation(&mut app).x - basket.x).abs();
+    assert!(moved > 0.);
+    assert!(moved <= player_speed * (max_delta + TICK).as_secs_f32() + 1e-3);
+    assert!(!exists(&app, apple));
+    assert!(app.world().resource::<Scoreboard>().score > score);
+}
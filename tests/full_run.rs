@@ -0,0 +1,102 @@
+mod common;
+
+use applecatcher::{
+    config::GameConfig,
+    game::{AppleKind, Bomb, Falling, OnGameScreen, Player},
+    main_menu,
+    pause_menu::MenuButtonAction,
+    Difficulty, GameState, PauseMode, Scoreboard,
+};
+use bevy::prelude::*;
+
+use common::*;
+
+const RUN_FRAMES: usize = 30 * 60;
+// How far off the basket may be before it chases an apple.
+const STEER_DEADBAND: f32 = 8.;
+// Further than this and walking won't get there in time.
+const DASH_RANGE: f32 = 150.;
+// A bomb this close above the basket is dodged rather than waited out.
+const BOMB_CLEARANCE: Vec2 = Vec2::new(160., 300.);
+
+// Chases the lowest apple still worth catching and gets out from under
+// bombs, the way a player would.
+fn steer(app: &mut App) {
+    let world = app.world_mut();
+    let basket = world
+        .query_filtered::<&Transform, With<Player>>()
+        .single(world)
+        .translation;
+    let mut falling =
+        world.query_filtered::<(&Transform, Option<&AppleKind>, Has<Bomb>), With<Falling>>();
+    let above = |transform: &Transform| transform.translation.y > basket.y;
+    let bomb = falling
+        .iter(world)
+        .filter(|(transform, _, is_bomb)| {
+            *is_bomb
+                && above(transform)
+                && (transform.translation.x - basket.x).abs() < BOMB_CLEARANCE.x
+                && transform.translation.y - basket.y < BOMB_CLEARANCE.y
+        })
+        .map(|(transform, ..)| transform.translation)
+        .next();
+    let apple = falling
+        .iter(world)
+        .filter(|(transform, kind, is_bomb)| {
+            !is_bomb && *kind != Some(&AppleKind::Rotten) && above(transform)
+        })
+        .map(|(transform, ..)| transform.translation)
+        .min_by(|a, b| a.y.total_cmp(&b.y));
+    let offset = match (bomb, apple) {
+        (Some(bomb), _) if bomb.x > basket.x => -f32::INFINITY,
+        (Some(_), _) => f32::INFINITY,
+        (None, Some(apple)) => apple.x - basket.x,
+        (None, None) => 0.,
+    };
+    let script = &mut world.resource_mut::<ScriptedActions>().0;
+    script.left = offset < -STEER_DEADBAND;
+    script.right = offset > STEER_DEADBAND;
+    script.dash = offset.abs() > DASH_RANGE;
+}
+
+#[test]
+fn a_whole_run_from_the_menu_and_back() {
+    let mut app = headless_app();
+    boot(&mut app);
+    app.world_mut().resource_mut::<GameConfig>().seed = Some(2024);
+    click(&mut app, |action| {
+        matches!(
+            action,
+            main_menu::MenuButtonAction::SetDifficulty(Difficulty::Easy)
+        )
+    });
+    press_play(&mut app);
+
+    for _ in 0..RUN_FRAMES {
+        steer(&mut app);
+        app.update();
+    }
+    app.world_mut().resource_mut::<ScriptedActions>().0 = default();
+    assert_eq!(game_state(&app), GameState::Game);
+    assert!(app.world().resource::<Scoreboard>().score > 0);
+
+    press_pause(&mut app);
+    assert_eq!(pause_mode(&app), Some(PauseMode::Paused));
+    press_pause(&mut app);
+    step_until(&mut app, |app| pause_mode(app) == Some(PauseMode::Playing));
+    assert_eq!(pause_mode(&app), Some(PauseMode::Playing));
+
+    press_pause(&mut app);
+    click(&mut app, |action| {
+        matches!(action, MenuButtonAction::MainMenu)
+    });
+    step_until(&mut app, |app| game_state(app) == GameState::MainMenu);
+    let world = app.world_mut();
+    assert_eq!(
+        world
+            .query_filtered::<(), With<OnGameScreen>>()
+            .iter(world)
+            .count(),
+        0
+    );
+}
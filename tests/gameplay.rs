@@ -0,0 +1,67 @@
+mod common;
+
+use applecatcher::{
+    game::{Apple, Player},
+    GameState, PauseMode, PlayArea, Scoreboard,
+};
+use bevy::prelude::*;
+
+use common::*;
+
+#[test]
+fn catching_an_apple_scores_and_despawns_it() {
+    let mut app = start_game();
+    let basket = player_translation(&mut app);
+    let apple = spawn_test_apple(&mut app, basket + Vec3::Y * 80.);
+    let score = app.world().resource::<Scoreboard>().score;
+
+    step_until(&mut app, |app| !exists(app, apple));
+
+    assert!(app.world().resource::<Scoreboard>().score > score);
+}
+
+#[test]
+fn an_apple_below_the_bottom_despawns() {
+    let mut app = start_game();
+    let bottom = -app.world().resource::<PlayArea>().height / 2.;
+    let apple = spawn_test_apple(&mut app, Vec3::new(0., bottom - 200., 0.));
+
+    step(&mut app, 2);
+
+    assert!(!exists(&app, apple));
+}
+
+#[test]
+fn pausing_stops_apples() {
+    let mut app = start_game();
+    let apple = spawn_test_apple(&mut app, Vec3::new(200., 0., 0.));
+    step(&mut app, 10);
+    app.world_mut()
+        .resource_mut::<NextState<PauseMode>>()
+        .set(PauseMode::Paused);
+    step(&mut app, 1);
+    let paused_at = app.world().get::<Transform>(apple).unwrap().translation;
+
+    step(&mut app, 60);
+
+    assert_eq!(pause_mode(&app), Some(PauseMode::Paused));
+    assert!(app.world().get::<Apple>(apple).is_some());
+    assert_eq!(
+        app.world().get::<Transform>(apple).unwrap().translation,
+        paused_at
+    );
+}
+
+// As on the frames before `setup` has spawned the basket.
+#[test]
+fn the_game_runs_without_a_basket() {
+    let mut app = start_game();
+    let world = app.world_mut();
+    let player = world.query_filtered::<Entity, With<Player>>().single(world);
+    world.entity_mut(player).despawn_recursive();
+    spawn_test_apple(&mut app, Vec3::ZERO);
+
+    step(&mut app, 60);
+
+    assert_eq!(game_state(&app), GameState::Game);
+}
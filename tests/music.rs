@@ -0,0 +1,20 @@
+mod common;
+
+use bevy::{audio::PlaybackMode, prelude::*};
+
+use common::*;
+
+#[test]
+fn an_empty_music_folder_plays_nothing() {
+    let mut app = start_game();
+    step(&mut app, 120);
+
+    // Sound effects despawn once they finish, unlike music.
+    let world = app.world_mut();
+    let music = world
+        .query::<&PlaybackSettings>()
+        .iter(world)
+        .filter(|settings| !matches!(settings.mode, PlaybackMode::Despawn))
+        .count();
+    assert_eq!(music, 0);
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use applecatcher::PauseMode;
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use common::*;
+
+fn tap(app: &mut App, key_code: KeyCode, logical_key: Key) {
+    for state in [ButtonState::Pressed, ButtonState::Released] {
+        app.world_mut().send_event(KeyboardInput {
+            key_code,
+            logical_key: logical_key.clone(),
+            state,
+            window: Entity::PLACEHOLDER,
+        });
+    }
+}
+
+#[test]
+fn one_escape_press_per_frame_strictly_alternates() {
+    let mut app = start_game();
+    app.world_mut().remove_resource::<ScriptedActions>();
+
+    let mut paused = Vec::new();
+    for _ in 0..20 {
+        tap(&mut app, KeyCode::Escape, Key::Escape);
+        app.update();
+        paused.push(pause_mode(&app) == Some(PauseMode::Paused));
+    }
+
+    // Each press lands on the following frame's state transition.
+    assert!(!paused[0]);
+    assert!(paused[1]);
+    for (frame, pair) in paused.windows(2).enumerate().skip(1) {
+        assert_ne!(pair[0], pair[1], "frame {}", frame + 1);
+    }
+}